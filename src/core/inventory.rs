@@ -2,6 +2,13 @@ use std::collections::HashMap;
 
 use crate::core::item::*;
 
+// Less of the item than asked for, nothing was removed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotEnough {
+    pub item: ItemEnum,
+    pub available: usize,
+}
+
 #[derive(Debug)]
 pub struct Inventory {
     items: HashMap<ItemEnum, usize>, // item id, item quantity
@@ -41,18 +48,18 @@ impl Inventory {
             .collect()
     }
 
-    pub fn remove(&mut self, id: ItemEnum, qty: usize) -> usize {
-        match self.items.get(&id) {
-            None => 0,
-            Some(current_qty) => {
-                if *current_qty < qty {
-                    panic!("Not enought {:?} on inventory", id)
-                }
-
-                let rest = *current_qty - qty;
-                self.items.insert(id, rest);
-                rest
-            }
+    // Returns what is left of the item
+    pub fn remove(&mut self, id: ItemEnum, qty: usize) -> Result<usize, NotEnough> {
+        let current_qty = self.get_qty(id);
+        if current_qty < qty {
+            return Err(NotEnough {
+                item: id,
+                available: current_qty,
+            });
         }
+
+        let rest = current_qty - qty;
+        self.items.insert(id, rest);
+        Ok(rest)
     }
 }
@@ -1 +0,0 @@
-pub type Location = [f32; 3];
@@ -1,7 +1,3 @@
-pub mod action;
 pub mod inventory;
-pub mod task;
-pub mod location;
-pub mod needs;
 pub mod item;
-pub mod role;
+pub mod needs;
@@ -21,11 +21,11 @@ impl Needs {
     }
 
     pub fn is_hungry(&self) -> bool {
-        self.hunger > 1000 
+        self.hunger > 1000
     }
 
     pub fn is_thirsty(&self) -> bool {
-        self.thirst > 1000 
+        self.thirst > 1000
     }
 
     pub fn satisfy_hunger(&mut self) {
@@ -0,0 +1,166 @@
+use bevy::prelude::*;
+
+use crate::ecs::traits::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ActionKind {
+    Walk,
+    Buy,
+    Consume,
+    Sell,
+    Harvest,
+    Apply,
+    Work,
+    SellBack,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionFailure {
+    TimedOut,
+    TargetNotFound,
+    InteractionFailed,
+    Blocked,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionState {
+    Created,
+    InProgress,
+    Completed,
+    Failed(ActionFailure),
+    Cancelled,
+}
+
+/// Every action component (Walking, Buying, Consuming, Selling...) implements this
+/// so the generic systems can start, progress, pause, time out and finish it.
+pub trait Action: Component {
+    const KIND: ActionKind;
+    // Actions that can't go on while the agent is busy with an interaction
+    const PAUSE_WHILE_INTERACTING: bool = false;
+
+    fn lifecycle(&self) -> &ActionLifecycle;
+    fn lifecycle_mut(&mut self) -> &mut ActionLifecycle;
+}
+
+#[derive(Debug, Clone)]
+pub struct ActionLifecycle {
+    state: ActionState,
+    elapsed: f32,
+    duration: Option<f32>,
+    timeout: Option<f32>,
+    paused: Paused,
+    set_idle_at_completion: bool,
+}
+
+impl ActionLifecycle {
+    pub fn new() -> Self {
+        Self {
+            state: ActionState::Created,
+            elapsed: 0.,
+            duration: None,
+            timeout: None,
+            paused: Paused::default(),
+            set_idle_at_completion: false,
+        }
+    }
+
+    pub fn with_duration(duration: f32) -> Self {
+        Self {
+            duration: Some(duration),
+            ..Self::new()
+        }
+    }
+
+    pub fn timeout_after(mut self, timeout: f32) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn idle_at_completion(mut self) -> Self {
+        self.set_idle_at_completion = true;
+        self
+    }
+
+    pub fn state(&self) -> ActionState {
+        self.state
+    }
+
+    pub fn is_created(&self) -> bool {
+        self.state == ActionState::Created
+    }
+
+    pub fn is_in_progress(&self) -> bool {
+        self.state == ActionState::InProgress
+    }
+
+    pub fn is_finished(&self) -> bool {
+        !matches!(self.state, ActionState::Created | ActionState::InProgress)
+    }
+
+    pub fn start(&mut self) {
+        if self.is_created() {
+            self.state = ActionState::InProgress;
+        }
+    }
+
+    pub fn complete(&mut self) {
+        if !self.is_finished() {
+            self.state = ActionState::Completed;
+        }
+    }
+
+    pub fn fail(&mut self, reason: ActionFailure) {
+        if !self.is_finished() {
+            self.state = ActionState::Failed(reason);
+        }
+    }
+
+    pub fn cancel(&mut self) {
+        if !self.is_finished() {
+            self.state = ActionState::Cancelled;
+        }
+    }
+
+    pub fn progress(&mut self, time: f32) {
+        self.elapsed += time;
+    }
+
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    pub fn get_resting_duration(&self) -> Option<f32> {
+        self.duration.map(|duration| duration - self.elapsed)
+    }
+
+    // Duration actions are done once the whole duration has elapsed
+    pub fn has_elapsed(&self) -> bool {
+        match self.duration {
+            Some(duration) => self.elapsed >= duration,
+            None => false,
+        }
+    }
+
+    pub fn is_timed_out(&self) -> bool {
+        match self.timeout {
+            Some(timeout) => self.elapsed >= timeout,
+            None => false,
+        }
+    }
+
+    pub fn should_set_idle_at_completion(&self) -> bool {
+        self.set_idle_at_completion
+    }
+}
+
+impl Pausable for ActionLifecycle {
+    fn pause(&mut self, reason: PauseReason) {
+        self.paused.insert(reason);
+    }
+    fn resume(&mut self, reason: PauseReason) {
+        self.paused.remove(&reason);
+    }
+    fn is_paused(&self) -> bool {
+        !self.paused.is_empty()
+    }
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+use crate::ecs::action::components::{ActionFailure, ActionKind};
+
+#[derive(Event, Debug)]
+pub struct ActionStarted {
+    pub target: Entity,
+    pub kind: ActionKind,
+}
+
+// Sent every frame an action makes progress, so it goes through
+// EventWriter instead of being triggered like the other lifecycle events
+#[derive(Event, Debug)]
+pub struct ActionProgressed {
+    pub target: Entity,
+    pub kind: ActionKind,
+    pub elapsed: f32,
+}
+
+#[derive(Event, Debug)]
+pub struct ActionCompleted {
+    pub target: Entity,
+    pub kind: ActionKind,
+}
+
+#[derive(Event, Debug)]
+pub struct ActionFailed {
+    pub target: Entity,
+    pub kind: ActionKind,
+    pub reason: ActionFailure,
+}
+
+#[derive(Event, Debug)]
+pub struct ActionCancelled {
+    pub target: Entity,
+    pub kind: ActionKind,
+}
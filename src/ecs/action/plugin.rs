@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+
+use crate::ecs::{
+    action::{components::Action, events::*, resources::ActionRegistry, systems::*},
+    game_state::GameState,
+};
+
+pub struct ActionPlugin;
+
+impl Plugin for ActionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActionRegistry>()
+            .add_event::<ActionStarted>()
+            .add_event::<ActionProgressed>()
+            .add_event::<ActionCompleted>()
+            .add_event::<ActionFailed>()
+            .add_event::<ActionCancelled>()
+            .add_observer(log_action_started)
+            .add_observer(log_action_completed)
+            .add_observer(log_action_failed)
+            .add_observer(log_action_cancelled);
+    }
+}
+
+pub trait RegisterAction {
+    fn register_action<A: Action>(&mut self) -> &mut Self;
+}
+
+impl RegisterAction for App {
+    // Each action plugin registers its component here to get the generic
+    // lifecycle handling (start, progress, timeout, pause and finish)
+    fn register_action<A: Action>(&mut self) -> &mut Self {
+        let component_id = self.world_mut().register_component::<A>();
+        self.world_mut()
+            .get_resource_or_insert_with(ActionRegistry::default)
+            .register::<A>(component_id);
+
+        self.add_systems(
+            PreUpdate,
+            progress_action_system::<A>.run_if(in_state(GameState::Running)),
+        )
+        .add_systems(
+            PostUpdate,
+            (
+                pause_action_on_interaction_added::<A>,
+                finish_action_system::<A>,
+            )
+                .chain()
+                .run_if(in_state(GameState::Running)),
+        )
+        .add_observer(resume_action_on_interaction_removed::<A>)
+    }
+}
@@ -0,0 +1,46 @@
+use bevy::{
+    ecs::{
+        component::ComponentId,
+        system::Resource,
+        world::{EntityRef, EntityWorldMut},
+    },
+    utils::HashMap,
+};
+
+use crate::ecs::action::components::{Action, ActionKind};
+
+type CancelActionFn = fn(&mut EntityWorldMut);
+
+// Filled by `register_action`, so generic systems can look for (and cancel)
+// any action on an entity without knowing every action component
+#[derive(Resource, Default, Debug, Clone)]
+pub struct ActionRegistry {
+    actions: HashMap<ActionKind, (ComponentId, CancelActionFn)>,
+}
+
+impl ActionRegistry {
+    pub fn register<A: Action>(&mut self, id: ComponentId) {
+        self.actions.insert(A::KIND, (id, cancel_action::<A>));
+    }
+
+    pub fn find_on(&self, entity: &EntityRef) -> Vec<ActionKind> {
+        self.actions
+            .iter()
+            .filter(|(_, (id, _))| entity.contains_id(*id))
+            .map(|(kind, _)| *kind)
+            .collect()
+    }
+
+    // The generic finish system removes the cancelled actions afterwards
+    pub fn cancel_all_on(&self, entity: &mut EntityWorldMut) {
+        for (_, cancel) in self.actions.values() {
+            cancel(entity);
+        }
+    }
+}
+
+fn cancel_action<A: Action>(entity: &mut EntityWorldMut) {
+    if let Some(mut action) = entity.get_mut::<A>() {
+        action.lifecycle_mut().cancel();
+    }
+}
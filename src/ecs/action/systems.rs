@@ -0,0 +1,150 @@
+use bevy::prelude::*;
+
+use crate::ecs::{
+    action::{
+        components::{Action, ActionFailure, ActionState},
+        events::*,
+    },
+    components::{Idle, Interacting},
+    logs::AddLogEntry,
+    traits::*,
+};
+
+pub fn progress_action_system<A: Action>(
+    mut query: Query<(Entity, &mut A)>,
+    mut progressed_writer: EventWriter<ActionProgressed>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    for (entity, mut action) in &mut query {
+        let lifecycle = action.lifecycle_mut();
+
+        if lifecycle.is_created() {
+            lifecycle.start();
+            commands.trigger(ActionStarted {
+                target: entity,
+                kind: A::KIND,
+            });
+            continue;
+        }
+
+        if !lifecycle.is_in_progress() || lifecycle.is_paused() {
+            continue;
+        }
+
+        lifecycle.progress(time.delta_secs());
+
+        if lifecycle.is_timed_out() {
+            lifecycle.fail(ActionFailure::TimedOut);
+            continue;
+        }
+
+        progressed_writer.send(ActionProgressed {
+            target: entity,
+            kind: A::KIND,
+            elapsed: lifecycle.elapsed(),
+        });
+    }
+}
+
+pub fn finish_action_system<A: Action>(query: Query<(Entity, &A)>, mut commands: Commands) {
+    for (entity, action) in &query {
+        let lifecycle = action.lifecycle();
+
+        // lifecycle events are triggered before removing the component,
+        // so observers can still read the finished action
+        match lifecycle.state() {
+            ActionState::Completed => commands.trigger(ActionCompleted {
+                target: entity,
+                kind: A::KIND,
+            }),
+            ActionState::Failed(reason) => commands.trigger(ActionFailed {
+                target: entity,
+                kind: A::KIND,
+                reason,
+            }),
+            ActionState::Cancelled => commands.trigger(ActionCancelled {
+                target: entity,
+                kind: A::KIND,
+            }),
+            ActionState::Created | ActionState::InProgress => continue,
+        }
+
+        if lifecycle.should_set_idle_at_completion() {
+            commands.entity(entity).insert(Idle).remove::<A>();
+        } else {
+            commands.entity(entity).remove::<A>();
+        }
+    }
+}
+
+// Covers both an agent starting to interact during the action and an action
+// added to an agent already interacting
+pub fn pause_action_on_interaction_added<A: Action>(mut query: Query<(&mut A, Ref<Interacting>)>) {
+    if !A::PAUSE_WHILE_INTERACTING {
+        return;
+    }
+
+    for (mut action, interacting) in &mut query {
+        if interacting.is_added() || action.is_added() {
+            action.lifecycle_mut().pause(PauseReason::Interacting);
+        }
+    }
+}
+
+pub fn resume_action_on_interaction_removed<A: Action>(
+    trigger: Trigger<OnRemove, Interacting>,
+    mut query: Query<&mut A>,
+) {
+    if !A::PAUSE_WHILE_INTERACTING {
+        return;
+    }
+
+    if let Ok(mut action) = query.get_mut(trigger.entity()) {
+        action.lifecycle_mut().resume(PauseReason::Interacting);
+    }
+}
+
+pub fn log_action_started(
+    trigger: Trigger<ActionStarted>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+) {
+    add_log_writer.send(AddLogEntry::new(
+        trigger.target,
+        format!("{:?} action started", trigger.kind).as_str(),
+    ));
+}
+
+pub fn log_action_completed(
+    trigger: Trigger<ActionCompleted>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+) {
+    add_log_writer.send(AddLogEntry::new(
+        trigger.target,
+        format!("{:?} action finished with success", trigger.kind).as_str(),
+    ));
+}
+
+pub fn log_action_failed(
+    trigger: Trigger<ActionFailed>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+) {
+    add_log_writer.send(AddLogEntry::new(
+        trigger.target,
+        format!(
+            "{:?} action finished with FAILURE ({:?})",
+            trigger.kind, trigger.reason
+        )
+        .as_str(),
+    ));
+}
+
+pub fn log_action_cancelled(
+    trigger: Trigger<ActionCancelled>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+) {
+    add_log_writer.send(AddLogEntry::new(
+        trigger.target,
+        format!("{:?} action cancelled", trigger.kind).as_str(),
+    ));
+}
@@ -1,7 +1,11 @@
-use crate::core::{inventory::*, item::ItemEnum, needs::*};
+use crate::{
+    core::{inventory::*, item::ItemEnum, needs::*},
+    ecs::credit::components::Debts,
+};
 use bevy::prelude::*;
 
 #[derive(Component, Debug)]
+#[require(Debts)]
 pub struct Agent {
     pub needs: Needs,
     pub inventory: Inventory,
@@ -18,7 +22,8 @@ impl Agent {
     pub fn new_seller_of(item: ItemEnum) -> Self {
         let mut inv = Inventory::new();
 
-        inv.add(item, 5000);
+        // enough to open the shop, the rest has to be harvested
+        inv.add(item, 20);
 
         Self {
             needs: Needs::new(),
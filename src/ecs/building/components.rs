@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuildingKind {
+    Shop,
+    House,
+    Workshop,
+    Well,
+    MarketStall,
+}
+
+impl BuildingKind {
+    // Size in tiles
+    pub fn footprint(&self) -> UVec2 {
+        match self {
+            BuildingKind::Shop | BuildingKind::House => UVec2::new(2, 2),
+            BuildingKind::Workshop => UVec2::new(3, 2),
+            BuildingKind::Well | BuildingKind::MarketStall => UVec2::new(1, 1),
+        }
+    }
+
+    // How many agents it is meant for: sellers, residents, workers...
+    pub fn capacity(&self) -> usize {
+        match self {
+            BuildingKind::Shop | BuildingKind::MarketStall => 1,
+            BuildingKind::House => 4,
+            BuildingKind::Workshop => 3,
+            BuildingKind::Well => 0,
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            BuildingKind::Shop => Color::srgb(0.7, 0.45, 0.2),
+            BuildingKind::House => Color::srgb(0.6, 0.3, 0.25),
+            BuildingKind::Workshop => Color::srgb(0.4, 0.4, 0.45),
+            BuildingKind::Well => Color::srgb(0.3, 0.55, 0.8),
+            BuildingKind::MarketStall => Color::srgb(0.85, 0.7, 0.3),
+        }
+    }
+}
+
+// Sits on the tiles of its footprint, its Transform at their center. Agents
+// use it from the entrance, the tile right south of it.
+#[derive(Component, Debug)]
+pub struct Building {
+    pub kind: BuildingKind,
+    pub footprint: UVec2,
+    pub entrance: Vec3,
+    pub owner: Option<Entity>,
+    pub capacity: usize,
+    occupants: Vec<Entity>,
+}
+
+impl Building {
+    pub fn new(kind: BuildingKind, entrance: Vec3) -> Self {
+        Self {
+            kind,
+            footprint: kind.footprint(),
+            entrance,
+            owner: None,
+            capacity: kind.capacity(),
+            occupants: vec![],
+        }
+    }
+
+    pub fn has_room(&self) -> bool {
+        self.occupants.len() < self.capacity
+    }
+
+    // The first occupant owns the place, unless it already has an owner
+    pub fn add_occupant(&mut self, agent: Entity) -> bool {
+        if !self.has_room() {
+            return false;
+        }
+
+        self.occupants.push(agent);
+        self.owner.get_or_insert(agent);
+        true
+    }
+
+    pub fn occupants(&self) -> &[Entity] {
+        &self.occupants
+    }
+}
+
+// The house an agent lives in
+#[derive(Component, Debug)]
+pub struct Resident {
+    pub home: Entity,
+}
@@ -0,0 +1,4 @@
+pub mod components;
+pub mod plugin;
+mod systems;
+pub mod utils;
@@ -0,0 +1,11 @@
+use bevy::prelude::*;
+
+use crate::ecs::building::systems::*;
+
+pub struct BuildingPlugin;
+
+impl Plugin for BuildingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(register_landmark);
+    }
+}
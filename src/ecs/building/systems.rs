@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+
+use crate::ecs::{building::components::Building, knowledge::SharedKnowledge};
+
+// Everyone can find a building once they know about it
+pub fn register_landmark(
+    trigger: Trigger<OnAdd, Building>,
+    query: Query<&Building>,
+    mut shared_knowledge: ResMut<SharedKnowledge>,
+) {
+    if let Ok(building) = query.get(trigger.entity()) {
+        shared_knowledge.add_landmark(trigger.entity(), building.entrance);
+    }
+}
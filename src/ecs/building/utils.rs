@@ -0,0 +1,94 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::ecs::{
+    agent::Agent,
+    building::components::{Building, BuildingKind},
+    components::Walking,
+    map::resources::{PathCache, WorldMap},
+    movement::resources::MovementConfig,
+    roles::seller::SellerRole,
+};
+
+// Places a building on a free site of the map, its footprint becoming
+// impassable, with `occupants` living or working in it. Returns the building
+// and its entrance.
+pub fn spawn_building(
+    commands: &mut Commands,
+    map: &mut WorldMap,
+    kind: BuildingKind,
+    occupants: &[Entity],
+) -> Option<(Entity, Vec3)> {
+    spawn_building_clear_of(commands, map, kind, occupants, &[])
+}
+
+// Like spawn_building, away from the `keep_clear` points and their radius
+fn spawn_building_clear_of(
+    commands: &mut Commands,
+    map: &mut WorldMap,
+    kind: BuildingKind,
+    occupants: &[Entity],
+    keep_clear: &[(Vec3, f32)],
+) -> Option<(Entity, Vec3)> {
+    let footprint = kind.footprint();
+    let tile = map.find_site(footprint, keep_clear)?;
+    map.build(tile, footprint);
+
+    let entrance_tile = UVec2::new(tile.x + footprint.x / 2, tile.y + footprint.y);
+    let entrance = map.tile_center(entrance_tile);
+    let center = (map.tile_center(tile) + map.tile_center(tile + footprint - UVec2::ONE)) / 2.;
+
+    let mut building = Building::new(kind, entrance);
+    for occupant in occupants {
+        building.add_occupant(*occupant);
+    }
+
+    let entity = commands
+        .spawn((
+            Sprite {
+                color: kind.color(),
+                custom_size: Some(footprint.as_vec2() * map.tile_size),
+                ..default()
+            },
+            Transform::from_translation(center.with_z(-5.)),
+            Name::new(format!("{:?}", kind)),
+            building,
+        ))
+        .id();
+
+    Some((entity, entrance))
+}
+
+// Building once the game runs: the site stays clear of agents, of where they
+// are walking to and of the queues around stalls, and cached routes are
+// dropped since they may now cross the building
+#[derive(SystemParam)]
+pub struct BuildingSite<'w, 's> {
+    map: ResMut<'w, WorldMap>,
+    path_cache: ResMut<'w, PathCache>,
+    agent_query: Query<'w, 's, (&'static Transform, Option<&'static Walking>), With<Agent>>,
+    seller_query: Query<'w, 's, &'static SellerRole>,
+    movement_config: Res<'w, MovementConfig>,
+}
+
+impl BuildingSite<'_, '_> {
+    pub fn spawn(&mut self, commands: &mut Commands, kind: BuildingKind) -> Option<(Entity, Vec3)> {
+        let keep_clear: Vec<(Vec3, f32)> = self
+            .agent_query
+            .iter()
+            .flat_map(|(transform, walking)| {
+                [Some(transform.translation), walking.map(|v| v.destination)]
+            })
+            .flatten()
+            .map(|point| (point, 0.))
+            .chain(
+                self.seller_query
+                    .iter()
+                    .map(|v| (v.location, self.movement_config.interaction_radius)),
+            )
+            .collect();
+
+        let spawned = spawn_building_clear_of(commands, &mut self.map, kind, &[], &keep_clear)?;
+        self.path_cache.clear();
+        Some(spawned)
+    }
+}
@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
-use crate::{core::item::ItemEnum, ecs::components::InteractionId};
+use crate::{
+    core::item::ItemEnum,
+    ecs::{action::components::*, components::InteractionId},
+};
 
 #[derive(Component)]
 pub struct Buying {
@@ -8,6 +11,7 @@ pub struct Buying {
     pub qty: usize,
     pub seller: Entity,
     pub interaction_id: Option<InteractionId>,
+    lifecycle: ActionLifecycle,
 }
 
 impl Buying {
@@ -17,17 +21,18 @@ impl Buying {
             item: item.clone(),
             seller,
             interaction_id: None,
+            lifecycle: ActionLifecycle::new().timeout_after(30.),
         }
     }
 }
 
-#[derive(Event)]
-pub struct BuyingSucceeded {
-    pub target: Entity,
-}
+impl Action for Buying {
+    const KIND: ActionKind = ActionKind::Buy;
 
-#[derive(Event)]
-pub struct BuyingFailed {
-    pub target: Entity,
-    pub seller: Entity,
+    fn lifecycle(&self) -> &ActionLifecycle {
+        &self.lifecycle
+    }
+    fn lifecycle_mut(&mut self) -> &mut ActionLifecycle {
+        &mut self.lifecycle
+    }
 }
@@ -1,32 +1,34 @@
 use bevy::prelude::*;
 
+use crate::ecs::action::components::{Action, ActionFailure};
 use crate::ecs::buy::actions::components::Buying;
-use crate::ecs::buy::actions::components::BuyingFailed;
 use crate::ecs::components::*;
 use crate::ecs::interaction::common::components::*;
-use crate::ecs::interaction::common::events::WaitingInteractionTimedOut;
+use crate::ecs::interaction::common::events::{InteractionEnded, InteractionRequested};
+use crate::ecs::knowledge::{AgentKnowledge, KnowledgeConfig, KnowledgeId, SharedKnowledge};
 use crate::ecs::logs::*;
-use crate::ecs::sell::actions::components::Selling;
 use crate::ecs::trade::components::*;
 
 pub fn handle_buy_action(
     mut query: Query<(Entity, &mut Buying), (Without<Interacting>, Without<WaitingInteraction>)>,
-    mut query_seller: Query<&mut AgentInteractionQueue, With<Selling>>,
+    query_seller: Query<(), With<AgentInteractionQueue>>,
     mut add_log_writer: EventWriter<AddLogEntry>,
     mut commands: Commands,
 ) {
     for (buyer, mut buying) in &mut query {
-        if let Ok(mut seller_agent_interaction_queue) = query_seller.get_mut(buying.seller) {
+        if !buying.lifecycle().is_in_progress() {
+            continue;
+        }
+
+        if query_seller.contains(buying.seller) {
             add_log_writer.send(AddLogEntry::new(
                 buyer,
-                "Seller found, adding TradeNegotiation to the seller queue",
+                "Seller found, requesting a TradeNegotiation",
             ));
 
             let waiting = WaitingInteraction::new(buyer, buying.seller);
             let interaction_id = waiting.id;
 
-            commands.entity(buyer).insert(waiting);
-
             let seller_trade_marker = TradeNegotiation {
                 role: TradeRole::Seller,
                 quantity: buying.qty,
@@ -35,49 +37,65 @@ pub fn handle_buy_action(
                 partner: buyer,
             };
 
-            seller_agent_interaction_queue.add(AgentInteractionItem {
-                id: interaction_id,
-                kind: AgentInteractionKind::Trade(seller_trade_marker),
+            commands.entity(buyer).insert(waiting);
+            commands.trigger(InteractionRequested {
+                source: buyer,
+                target: buying.seller,
+                item: AgentInteractionItem {
+                    id: interaction_id,
+                    kind: AgentInteractionKind::Trade(seller_trade_marker),
+                },
             });
 
             buying.interaction_id = Some(interaction_id);
         } else {
-            add_log_writer.send(AddLogEntry::new(buyer, "Seller not found, removing Buying"));
+            add_log_writer.send(AddLogEntry::new(buyer, "Seller not found, ending Buying"));
 
-            commands
-                .entity(buyer)
-                .remove::<Buying>()
-                .trigger(BuyingFailed {
-                    target: buyer,
-                    seller: buying.seller,
-                });
+            buying.lifecycle_mut().fail(ActionFailure::TargetNotFound);
         }
     }
 }
 
-pub fn handle_waiting_interaction_timed_out(
-    trigger: Trigger<WaitingInteractionTimedOut>,
-    agent_query: Query<(&WaitingInteraction, &Buying)>,
+pub fn handle_waiting_interaction_ended(
+    trigger: Trigger<InteractionEnded>,
+    mut agent_query: Query<(&WaitingInteraction, &mut Buying)>,
+    mut knowledge_query: Query<&mut AgentKnowledge>,
+    shared_knowledge: Res<SharedKnowledge>,
+    knowledge_config: Res<KnowledgeConfig>,
     mut add_log_writer: EventWriter<AddLogEntry>,
     mut commands: Commands,
 ) {
-    if let Ok((waiting_interaction, _)) = agent_query.get(trigger.source) {
+    if let Ok((waiting_interaction, mut buying)) = agent_query.get_mut(trigger.source) {
         if trigger.id == waiting_interaction.id {
             add_log_writer.send(AddLogEntry::new(
                 trigger.source,
                 format!(
-                    "Buying -> WaitingInteraction {} timed out, ending Buying",
-                    trigger.id
+                    "Buying -> WaitingInteraction {} ended ({:?}), ending Buying",
+                    trigger.id, trigger.reason
                 )
                 .as_str(),
             ));
             commands
                 .entity(trigger.source)
-                .remove::<(WaitingInteraction, Buying)>()
-                .trigger(BuyingFailed {
-                    target: trigger.source, // buyer
-                    seller: trigger.target, // seller
-                });
+                .remove::<WaitingInteraction>();
+
+            // what was heard about this seller is not true (anymore)
+            if trigger.reason == InteractionEndReason::Rejected(RejectionReason::NotSelling) {
+                if let Ok(mut knowledge) = knowledge_query.get_mut(trigger.source) {
+                    let facts: Vec<KnowledgeId> = knowledge
+                        .facts_about_seller(buying.seller, &buying.item, &shared_knowledge)
+                        .collect();
+                    for id in facts {
+                        knowledge.doubt(&id, &knowledge_config);
+                    }
+                }
+            }
+
+            let failure = match trigger.reason {
+                InteractionEndReason::TimedOut => ActionFailure::TimedOut,
+                _ => ActionFailure::InteractionFailed,
+            };
+            buying.lifecycle_mut().fail(failure);
         }
     }
 }
@@ -1,3 +1,3 @@
 pub mod actions;
-pub mod tasks;
 pub mod plugin;
+pub mod tasks;
@@ -5,9 +5,19 @@ use bevy::{
 };
 
 use crate::{
-    ecs::buy::{
-        actions::systems::{handle_buy_action, handle_waiting_interaction_timed_out},
-        tasks::systems::{handle_buy_task, handle_buying_failed},
+    ecs::{
+        action::plugin::RegisterAction,
+        buy::{
+            actions::{
+                components::Buying,
+                systems::{handle_buy_action, handle_waiting_interaction_ended},
+            },
+            tasks::{
+                components::BuyTask,
+                systems::{handle_buy_task, handle_buying_failed, handle_left_auction},
+            },
+        },
+        task::plugin::RegisterTask,
     },
     GameState,
 };
@@ -16,11 +26,14 @@ pub struct BuyPlugin;
 
 impl Plugin for BuyPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (handle_buy_task, handle_buy_action).run_if(in_state(GameState::Running)),
-        )
-        .add_observer(handle_buying_failed)
-        .add_observer(handle_waiting_interaction_timed_out);
+        app.register_action::<Buying>()
+            .register_task::<BuyTask>()
+            .add_systems(
+                Update,
+                (handle_buy_task, handle_buy_action).run_if(in_state(GameState::Running)),
+            )
+            .add_observer(handle_buying_failed)
+            .add_observer(handle_left_auction)
+            .add_observer(handle_waiting_interaction_ended);
     }
 }
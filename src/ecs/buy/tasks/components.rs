@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
-use crate::core::item::ItemEnum;
+use crate::{
+    core::item::ItemEnum,
+    ecs::task::components::{Task, TaskKind},
+};
 
 #[derive(Component, Debug)]
 pub struct BuyTask {
@@ -26,3 +29,7 @@ impl BuyTask {
         self.tried_sellers.push(seller);
     }
 }
+
+impl Task for BuyTask {
+    const KIND: TaskKind = TaskKind::Buy;
+}
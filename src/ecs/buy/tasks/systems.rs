@@ -1,82 +1,119 @@
 use bevy::prelude::*;
 
-use crate::ecs::buy::actions::components::{Buying, BuyingFailed};
+use crate::ecs::action::components::ActionKind;
+use crate::ecs::action::events::ActionFailed;
+use crate::ecs::agent::Agent;
+use crate::ecs::buy::actions::components::Buying;
 use crate::ecs::buy::tasks::components::BuyTask;
 use crate::ecs::components::*;
-use crate::ecs::knowledge::AgentKnowledge;
+use crate::ecs::crowd::{components::QueuedAt, events::JoinSellerQueue};
+use crate::ecs::interaction::common::components::{InteractionEndReason, RejectionReason};
+use crate::ecs::interaction::group::{components::GroupMember, events::LeftGroupInteraction};
+use crate::ecs::knowledge::{AgentKnowledge, KnowledgeConfig, SharedKnowledge};
 use crate::ecs::logs::*;
+use crate::ecs::movement::components::MovementSpeed;
 use crate::ecs::roles::seller::SellerRole;
+use crate::ecs::spatial::SpatialGrid;
 use crate::ecs::talk::task::components::TalkTask;
+use crate::ecs::task::{commands::TaskCommandsExt, components::TaskFailure};
 
 pub fn handle_buy_task(
     mut query: Query<
-        (Entity, &Transform, &mut BuyTask, &AgentKnowledge),
+        (
+            Entity,
+            &Transform,
+            &mut BuyTask,
+            &AgentKnowledge,
+            &MovementSpeed,
+            Option<&QueuedAt>,
+        ),
         (
             Without<Interacting>,
             Without<WaitingInteraction>,
             Without<Buying>,
             Without<Walking>,
+            Without<GroupMember>,
         ),
     >,
-    mut query_seller: Query<&SellerRole, With<SellerRole>>,
+    query_seller: Query<(&SellerRole, &Agent)>,
+    grid: Res<SpatialGrid>,
+    shared_knowledge: Res<SharedKnowledge>,
+    knowledge_config: Res<KnowledgeConfig>,
     mut commands: Commands,
     mut add_log_writer: EventWriter<AddLogEntry>,
 ) {
-    for (buyer, buyer_transform, buy_task, buyer_knowledge) in &mut query {
+    for (buyer, buyer_transform, buy_task, buyer_knowledge, speed, queued) in &mut query {
         let mut some_seller_found = false;
 
-        let known_sellers = buyer_knowledge.get_sellers_of(&buy_task.item);
+        // Sellers in sight with the item on display come first, closest first
+        let mut in_sight: Vec<(Entity, f32)> = grid
+            .within_radius(
+                buyer_transform.translation,
+                knowledge_config.observation_radius,
+            )
+            .filter(|(entity, _)| {
+                query_seller
+                    .get(*entity)
+                    .is_ok_and(|(_, agent)| agent.inventory.get_qty(buy_task.item) > 0)
+            })
+            .collect();
+        in_sight.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        let known_sellers: Vec<Entity> = in_sight
+            .into_iter()
+            .map(|(seller, _)| seller)
+            .chain(
+                buyer_knowledge
+                    .rank_sellers_of(
+                        &buy_task.item,
+                        buyer_transform.translation,
+                        speed.current,
+                        &shared_knowledge,
+                        &knowledge_config,
+                    )
+                    .into_iter()
+                    .map(|(seller, _)| seller),
+            )
+            .collect();
 
         if known_sellers.len() < 1 {
-            add_log_writer.send(AddLogEntry::new(
-                buyer,
-                "Zero Known Sellers. Buy Task failed. Start TalkTask",
-            ));
-            commands
-                .entity(buyer)
-                .insert(TalkTask::new(buy_task.item))
-                .remove::<BuyTask>();
+            commands.fail_task::<BuyTask>(buyer, TaskFailure::NoKnownSellers);
+            commands.start_task(buyer, TalkTask::new(buy_task.item));
 
             continue;
         }
 
-        for (seller, _) in known_sellers {
+        for seller in known_sellers {
             if buy_task.tried(&seller) {
                 continue;
             }
 
             some_seller_found = true;
 
-            if let Ok(seller_role) = query_seller.get_mut(seller.clone()) {
-                if buyer_transform.translation.distance(seller_role.location) > 50. {
-                    add_log_writer.send(AddLogEntry::new(
-                        buyer,
-                        "Starting Walking to the seller location",
-                    ));
-                    let mut walking = Walking::new(seller_role.location);
-                    walking.set_idle_at_completion(false);
-                    commands.entity(buyer).insert(walking);
-                    // buy_task.pause(PauseReason::Walking);
+            if query_seller.contains(seller) {
+                // Buyers line up at the seller first, then start buying from
+                // their place in the queue (queued and not walking anymore).
+                // Those queued too far away wait for their turn to move up.
+                if let Some(queued) = queued.filter(|v| v.seller == seller) {
+                    if queued.within_reach {
+                        add_log_writer.send(AddLogEntry::new(buyer, "Start Buying"));
+                        // buy_task.pause(PauseReason::Buying);
+                        commands.entity(buyer).insert(Buying::new(
+                            &buy_task.item,
+                            buy_task.qty,
+                            seller,
+                        ));
+                    }
                 } else {
-                    add_log_writer.send(AddLogEntry::new(buyer, "Start Buying"));
-                    // buy_task.pause(PauseReason::Buying);
-                    commands.entity(buyer).insert(Buying::new(
-                        &buy_task.item,
-                        buy_task.qty,
-                        seller.clone(),
-                    ));
+                    commands.trigger(JoinSellerQueue { buyer, seller });
                 }
                 break;
             }
         }
 
         if !some_seller_found {
-            add_log_writer.send(AddLogEntry::new(buyer, "No seller found. BuyTask failed"));
-            add_log_writer.send(AddLogEntry::new(buyer, "Start TalkTask"));
-            commands
-                .entity(buyer)
-                .insert(TalkTask::new(buy_task.item))
-                .remove::<BuyTask>();
+            commands.fail_task::<BuyTask>(buyer, TaskFailure::AllSellersTried);
+            commands.start_task(buyer, TalkTask::new(buy_task.item));
             // commands
             //     .entity(buyer)
             //     .insert(Walking::new(get_random_vec3()))
@@ -85,9 +122,40 @@ pub fn handle_buy_task(
     }
 }
 
-pub fn handle_buying_failed(trigger: Trigger<BuyingFailed>, mut query: Query<&mut BuyTask>) {
-    if let Ok(mut buy_task) = query.get_mut(trigger.target) {
+pub fn handle_buying_failed(
+    trigger: Trigger<ActionFailed>,
+    mut query: Query<(&mut BuyTask, &Buying)>,
+) {
+    if trigger.kind != ActionKind::Buy {
+        return;
+    }
+
+    if let Ok((mut buy_task, buying)) = query.get_mut(trigger.target) {
         // buy_task.resume(PauseReason::Buying);
-        buy_task.add_tried(trigger.seller);
+        buy_task.add_tried(buying.seller);
+    }
+}
+
+// Bidders of a seller's auction: the winners got their unit, the outbid ones
+// look for another seller
+pub fn handle_left_auction(
+    trigger: Trigger<LeftGroupInteraction>,
+    mut query: Query<&mut BuyTask>,
+    mut commands: Commands,
+) {
+    if trigger.agent == trigger.host {
+        return;
+    }
+
+    let Ok(mut buy_task) = query.get_mut(trigger.agent) else {
+        return;
+    };
+
+    match trigger.reason {
+        InteractionEndReason::Completed => commands.succeed_task::<BuyTask>(trigger.agent),
+        InteractionEndReason::Rejected(RejectionReason::Declined) => {
+            buy_task.add_tried(trigger.host)
+        }
+        _ => {}
     }
 }
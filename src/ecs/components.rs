@@ -4,96 +4,111 @@ use bevy::{
 };
 use rand::random;
 
-pub trait ActionMarker {
-    fn should_set_idle_at_completion(&self) -> bool;
-    fn set_idle_at_completion(&mut self, value: bool);
-}
-
-pub trait DurationAction {
-    fn get_resting_duration(&self) -> f32;
-    fn progress(&mut self, time: f32);
-}
-
-pub trait TimeoutAction {
-    fn set_timed_out(&mut self);
-    fn is_timed_out(&self) -> bool;
-}
+use crate::ecs::action::components::*;
 
 #[derive(Component, Default)]
 pub struct Idle;
 
 pub type InteractionId = u32;
 
+// Lifecycle shared by every interaction (trade, talk...):
+// the source is Requested -> Queued -> Accepted while it holds a
+// WaitingInteraction, then the Interaction entity goes
+// Accepted -> Ready -> Active. Any of them may jump to Finished, which
+// always comes with an InteractionEnded event.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InteractionPhase {
+    #[default]
+    Requested,
+    Queued,
+    Accepted,
+    Ready,
+    Active,
+    Finished,
+}
+
+// Lives on its own entity, spawned when the target accepts the request.
+// It is the only place holding the interaction state; both participants
+// point to it through Interacting.
 #[derive(Component, Debug)]
-pub struct Interacting {
+pub struct AgentInteraction {
     pub id: InteractionId,
     pub source: Entity,
     pub target: Entity,
-    resting_duration: f32,
-    timed_out: bool,
-    status: InteractingStatusEnum,
-}
-
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
-pub enum InteractingStatusEnum {
-    #[default]
-    Waiting,
-    Ready,
-    Started,
+    // times the interaction out once its duration has elapsed
+    lifecycle: ActionLifecycle,
+    phase: InteractionPhase,
 }
 
-impl Interacting {
-    pub fn new_with_id(id: InteractionId, source: Entity, target: Entity) -> Self {
+impl AgentInteraction {
+    pub fn new(id: InteractionId, source: Entity, target: Entity) -> Self {
         Self {
             id,
-            target,
             source,
-            timed_out: false,
-            resting_duration: 10.,
-            status: InteractingStatusEnum::Waiting
+            target,
+            lifecycle: ActionLifecycle::with_duration(10.),
+            phase: InteractionPhase::Accepted,
         }
     }
 
-    pub fn is_waiting(&self) -> bool {
-        self.status == InteractingStatusEnum::Waiting
+    pub fn phase(&self) -> InteractionPhase {
+        self.phase
     }
 
-    pub fn is_ready(&self) -> bool {
-        self.status == InteractingStatusEnum::Ready
+    pub fn lifecycle(&self) -> &ActionLifecycle {
+        &self.lifecycle
     }
 
-    pub fn set_ready(&mut self) {
-        self.status = InteractingStatusEnum::Ready
+    pub fn lifecycle_mut(&mut self) -> &mut ActionLifecycle {
+        &mut self.lifecycle
     }
 
-    pub fn set_started(&mut self) {
-        self.status = InteractingStatusEnum::Started
+    pub fn partner_of(&self, entity: Entity) -> Entity {
+        if self.source == entity {
+            self.target
+        } else {
+            self.source
+        }
     }
-}
 
-impl TimeoutAction for Interacting {
-    fn set_timed_out(&mut self) {
-        self.timed_out = true;
+    pub fn is_accepted(&self) -> bool {
+        self.phase == InteractionPhase::Accepted
     }
 
-    fn is_timed_out(&self) -> bool {
-        self.timed_out
+    pub fn is_finished(&self) -> bool {
+        self.phase == InteractionPhase::Finished
     }
-}
 
-impl DurationAction for Interacting {
-    fn get_resting_duration(&self) -> f32 {
-        self.resting_duration
+    pub fn set_ready(&mut self) {
+        self.phase = InteractionPhase::Ready
     }
-    fn progress(&mut self, time: f32) {
-        self.resting_duration -= time;
+
+    pub fn set_active(&mut self) {
+        self.phase = InteractionPhase::Active
+    }
+
+    pub fn set_finished(&mut self) {
+        self.phase = InteractionPhase::Finished
+    }
+}
+
+// Relationship from a participant to its Interaction entity
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Interacting {
+    pub interaction: Entity,
+    pub id: InteractionId,
+}
+
+impl Interacting {
+    pub fn new(interaction: Entity, id: InteractionId) -> Self {
+        Self { interaction, id }
     }
 }
 
 #[derive(Component, Debug)]
 pub struct WaitingInteraction {
-    resting_duration: f32,
-    timed_out: bool,
+    lifecycle: ActionLifecycle,
+    phase: InteractionPhase,
     pub id: InteractionId,
     pub source: Entity,
     pub target: Entity,
@@ -103,70 +118,97 @@ impl WaitingInteraction {
     pub fn new(source: Entity, target: Entity) -> Self {
         Self {
             id: random(),
-            resting_duration: 5.,
+            lifecycle: ActionLifecycle::with_duration(5.),
             source,
             target,
-            timed_out: false
+            phase: InteractionPhase::Requested,
         }
     }
 
     pub fn new_with_duration(source: Entity, target: Entity, resting_duration: f32) -> Self {
         Self {
             id: random(),
-            resting_duration,
+            lifecycle: ActionLifecycle::with_duration(resting_duration),
             target,
             source,
-            timed_out: false
+            phase: InteractionPhase::Requested,
         }
     }
-}
 
-impl TimeoutAction for WaitingInteraction {
-    fn set_timed_out(&mut self) {
-        self.timed_out = true;
+    pub fn phase(&self) -> InteractionPhase {
+        self.phase
     }
 
-    fn is_timed_out(&self) -> bool {
-        self.timed_out
+    pub fn lifecycle(&self) -> &ActionLifecycle {
+        &self.lifecycle
+    }
+
+    pub fn lifecycle_mut(&mut self) -> &mut ActionLifecycle {
+        &mut self.lifecycle
     }
-}
 
-impl DurationAction for WaitingInteraction {
-    fn get_resting_duration(&self) -> f32 {
-        self.resting_duration
+    pub fn is_finished(&self) -> bool {
+        self.phase == InteractionPhase::Finished
     }
-    fn progress(&mut self, time: f32) {
-        self.resting_duration -= time;
+
+    pub fn set_queued(&mut self) {
+        self.phase = InteractionPhase::Queued
+    }
+
+    pub fn set_accepted(&mut self) {
+        self.phase = InteractionPhase::Accepted
+    }
+
+    pub fn set_finished(&mut self) {
+        self.phase = InteractionPhase::Finished
     }
 }
 
 #[derive(Component, Debug)]
 pub struct Walking {
     pub destination: Vec3,
-    should_set_idle: bool,
+    // how close counts as arrived, None for MovementConfig::arrival_radius
+    pub arrival_radius: Option<f32>,
+    // paths dropped in a row because the way was blocked, reset on reaching
+    // a waypoint
+    pub blocked_replans: u32,
+    lifecycle: ActionLifecycle,
 }
 
 impl Walking {
     pub fn new(destination: Vec3) -> Self {
         Self {
             destination,
-            should_set_idle: true,
+            arrival_radius: None,
+            blocked_replans: 0,
+            lifecycle: ActionLifecycle::new()
+                .timeout_after(60.)
+                .idle_at_completion(),
         }
     }
 
     pub fn new_without_idle(destination: Vec3) -> Self {
         Self {
             destination,
-            should_set_idle: false,
+            arrival_radius: None,
+            blocked_replans: 0,
+            lifecycle: ActionLifecycle::new().timeout_after(60.),
         }
     }
+
+    pub fn arriving_within(mut self, radius: f32) -> Self {
+        self.arrival_radius = Some(radius);
+        self
+    }
 }
 
-impl ActionMarker for Walking {
-    fn set_idle_at_completion(&mut self, value: bool) {
-        self.should_set_idle = value;
+impl Action for Walking {
+    const KIND: ActionKind = ActionKind::Walk;
+
+    fn lifecycle(&self) -> &ActionLifecycle {
+        &self.lifecycle
     }
-    fn should_set_idle_at_completion(&self) -> bool {
-        self.should_set_idle
+    fn lifecycle_mut(&mut self) -> &mut ActionLifecycle {
+        &mut self.lifecycle
     }
 }
@@ -1,12 +1,12 @@
 use bevy::prelude::*;
 
-use crate::{core::item::ItemEnum, ecs::components::DurationAction};
+use crate::{core::item::ItemEnum, ecs::action::components::*};
 
 #[derive(Component)]
 pub struct Consuming {
-    resting_duration: f32,
     pub item: ItemEnum,
     pub qty: usize,
+    lifecycle: ActionLifecycle,
 }
 
 impl Consuming {
@@ -14,16 +14,19 @@ impl Consuming {
         Self {
             item,
             qty,
-            resting_duration: 5. * (qty as f32),
+            lifecycle: ActionLifecycle::with_duration(5. * (qty as f32)),
         }
     }
 }
 
-impl DurationAction for Consuming {
-    fn get_resting_duration(&self) -> f32 {
-        self.resting_duration
+impl Action for Consuming {
+    const KIND: ActionKind = ActionKind::Consume;
+    const PAUSE_WHILE_INTERACTING: bool = true;
+
+    fn lifecycle(&self) -> &ActionLifecycle {
+        &self.lifecycle
     }
-    fn progress(&mut self, time: f32) {
-        self.resting_duration -= time;
+    fn lifecycle_mut(&mut self) -> &mut ActionLifecycle {
+        &mut self.lifecycle
     }
 }
@@ -1,23 +1,28 @@
 use bevy::prelude::*;
 
+use crate::ecs::action::components::{Action, ActionFailure};
 use crate::ecs::agent::Agent;
-use crate::ecs::components::*;
 use crate::ecs::consume::actions::components::Consuming;
 use crate::ecs::logs::*;
 
 pub fn handle_consuming_action(
-    mut query: Query<(Entity, &mut Agent, &mut Consuming), Without<Interacting>>,
-    time: Res<Time>,
-    mut commands: Commands,
+    mut query: Query<(Entity, &mut Agent, &mut Consuming)>,
     mut add_log_writer: EventWriter<AddLogEntry>,
 ) {
     for (entity, mut agent, mut consuming) in &mut query {
-        if consuming.get_resting_duration() > 0. {
-            consuming.progress(time.delta_secs());
+        if !consuming.lifecycle().is_in_progress() || !consuming.lifecycle().has_elapsed() {
             continue;
         }
 
         let item = consuming.item.clone();
+        // gone since the agent sat down to eat or drink
+        if agent.inventory.remove(item, consuming.qty).is_err() {
+            consuming
+                .lifecycle_mut()
+                .fail(ActionFailure::TargetNotFound);
+            continue;
+        }
+
         if item.is_food() {
             add_log_writer.send(AddLogEntry::new(entity, "Consume (eat) done"));
             agent.satisfy_hungry();
@@ -27,8 +32,7 @@ pub fn handle_consuming_action(
             add_log_writer.send(AddLogEntry::new(entity, "Consume (drink) done"));
             agent.satisfy_thirsty();
         }
-        agent.inventory.remove(item, consuming.qty);
 
-        commands.entity(entity).remove::<Consuming>();
+        consuming.lifecycle_mut().complete();
     }
 }
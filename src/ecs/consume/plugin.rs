@@ -1,7 +1,14 @@
 use bevy::prelude::*;
 
 use crate::{
-    ecs::consume::{actions::systems::handle_consuming_action, tasks::systems::*},
+    ecs::{
+        action::plugin::RegisterAction,
+        consume::{
+            actions::{components::Consuming, systems::handle_consuming_action},
+            tasks::{components::ConsumeTask, systems::*},
+        },
+        task::plugin::RegisterTask,
+    },
     GameState,
 };
 
@@ -9,13 +16,15 @@ pub struct ConsumePlugin;
 
 impl Plugin for ConsumePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (handle_consuming_action, handle_consume_task).run_if(in_state(GameState::Running)),
-        )
-        .add_systems(PostUpdate, handle_pause_while_consume_task)
-        .add_observer(handle_resume_consume_task_on_interacting_removed)
-        .add_observer(handle_resume_consume_task_on_consuming_removed)
-        .add_observer(handle_resume_consume_task_on_walking_removed);
+        app.register_action::<Consuming>()
+            .register_task::<ConsumeTask>()
+            .add_systems(
+                Update,
+                (handle_consuming_action, handle_consume_task).run_if(in_state(GameState::Running)),
+            )
+            .add_systems(PostUpdate, handle_pause_while_consume_task)
+            .add_observer(handle_resume_consume_task_on_interacting_removed)
+            .add_observer(handle_resume_consume_task_on_consuming_removed)
+            .add_observer(handle_resume_consume_task_on_walking_removed);
     }
 }
@@ -4,7 +4,10 @@ use bevy::prelude::*;
 
 use crate::{
     core::item::ItemEnum,
-    ecs::{traits::*, utils::get_random_vec3},
+    ecs::{
+        task::components::{Task, TaskKind},
+        traits::*,
+    },
 };
 
 #[derive(Component)]
@@ -16,9 +19,10 @@ pub struct ConsumeTask {
 }
 
 impl ConsumeTask {
-    pub fn new(item: ItemEnum, qty: usize) -> Self {
+    // `location` is where the agent goes to eat or drink
+    pub fn new(item: ItemEnum, qty: usize, location: Vec3) -> Self {
         Self {
-            location: get_random_vec3(),
+            location,
             item,
             qty,
             paused: HashSet::new(),
@@ -26,6 +30,10 @@ impl ConsumeTask {
     }
 }
 
+impl Task for ConsumeTask {
+    const KIND: TaskKind = TaskKind::Consume;
+}
+
 impl Pausable for ConsumeTask {
     fn pause(&mut self, reason: PauseReason) {
         self.paused.insert(reason);
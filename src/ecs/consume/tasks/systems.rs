@@ -4,17 +4,20 @@ use crate::ecs::components::*;
 use crate::ecs::consume::actions::components::Consuming;
 use crate::ecs::consume::tasks::components::ConsumeTask;
 use crate::ecs::logs::*;
+use crate::ecs::movement::resources::MovementConfig;
+use crate::ecs::task::commands::TaskCommandsExt;
 use crate::ecs::traits::*;
 
 pub fn handle_consume_task(
     mut query: Query<(Entity, &Transform, &ConsumeTask)>,
+    config: Res<MovementConfig>,
     mut commands: Commands,
     mut add_log_writer: EventWriter<AddLogEntry>,
 ) {
     for (entity, transform, consume_task) in &mut query {
         if consume_task.is_paused() {
             continue;
-        } else if consume_task.location.distance(transform.translation) > 50. {
+        } else if consume_task.location.distance(transform.translation) > config.arrival_radius {
             add_log_writer.send(AddLogEntry::new(entity, "Start Walking to consume"));
             let walking = Walking::new_without_idle(consume_task.location);
             commands.entity(entity).insert(walking);
@@ -47,7 +50,7 @@ pub fn handle_pause_while_consume_task(
                 "Pausing ConsumeTask due to Interacting",
             ));
             task.pause(PauseReason::Interacting);
-        } 
+        }
 
         if let Some(_) = maybe_consuming {
             add_log_writer.send(AddLogEntry::new(
@@ -55,7 +58,7 @@ pub fn handle_pause_while_consume_task(
                 "Pausing ConsumeTask due to Consuming",
             ));
             task.pause(PauseReason::Consuming);
-        } 
+        }
 
         if let Some(_) = maybe_walking {
             add_log_writer.send(AddLogEntry::new(
@@ -98,17 +101,9 @@ pub fn handle_resume_consume_task_on_walking_removed(
 pub fn handle_resume_consume_task_on_consuming_removed(
     trigger: Trigger<OnRemove, Consuming>,
     query: Query<&ConsumeTask>,
-    mut add_log_writer: EventWriter<AddLogEntry>,
     mut commands: Commands,
 ) {
     if let Ok(_) = query.get(trigger.entity()) {
-        add_log_writer.send(AddLogEntry::new(
-            trigger.entity(),
-            "Ending ConsumeTask after Consuming",
-        ));
-        commands
-            .entity(trigger.entity())
-            .insert(Idle)
-            .remove::<ConsumeTask>();
+        commands.succeed_task::<ConsumeTask>(trigger.entity());
     }
 }
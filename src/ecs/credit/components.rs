@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+
+// What is left to repay of a purchase made on credit, interest included
+#[derive(Debug, Clone)]
+pub struct Iou {
+    pub creditor: Entity,
+    pub principal: usize,
+    pub owed: usize,
+    // elapsed secs after which it is a default
+    pub due_at: f32,
+}
+
+#[derive(Component, Debug, Default)]
+pub struct Debts {
+    ious: Vec<Iou>,
+}
+
+impl Debts {
+    pub fn add(&mut self, iou: Iou) {
+        self.ious.push(iou);
+        self.ious.sort_by(|a, b| a.due_at.total_cmp(&b.due_at));
+    }
+
+    pub fn total_owed(&self) -> usize {
+        self.ious.iter().map(|iou| iou.owed).sum()
+    }
+
+    pub fn owed_to(&self, creditor: Entity) -> usize {
+        self.ious
+            .iter()
+            .filter(|iou| iou.creditor == creditor)
+            .map(|iou| iou.owed)
+            .sum()
+    }
+
+    pub fn ious(&self) -> impl Iterator<Item = &Iou> {
+        self.ious.iter()
+    }
+
+    // Pays what `money` allows, soonest due first. An IOU is only paid down
+    // when `transfer` moved the money to its creditor. Returns each creditor
+    // paid, how much, and whether that IOU is settled.
+    pub fn repay(
+        &mut self,
+        mut money: usize,
+        mut transfer: impl FnMut(Entity, usize) -> bool,
+    ) -> Vec<(Entity, usize, bool)> {
+        let mut payments = vec![];
+        for iou in self.ious.iter_mut() {
+            if money == 0 {
+                break;
+            }
+            let amount = money.min(iou.owed);
+            if !transfer(iou.creditor, amount) {
+                continue;
+            }
+            iou.owed -= amount;
+            money -= amount;
+            payments.push((iou.creditor, amount, iou.owed == 0));
+        }
+        self.ious.retain(|iou| iou.owed > 0);
+        payments
+    }
+
+    // Drops everything owed to the creditor, returns how much it was
+    pub fn write_off(&mut self, creditor: Entity) -> usize {
+        let owed = self.owed_to(creditor);
+        self.ious.retain(|iou| iou.creditor != creditor);
+        owed
+    }
+
+    // Removes and returns the IOUs past their due date
+    pub fn take_overdue(&mut self, now: f32) -> Vec<Iou> {
+        let (overdue, current) = self.ious.drain(..).partition(|iou| iou.due_at < now);
+        self.ious = current;
+        overdue
+    }
+}
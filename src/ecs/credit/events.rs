@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+
+use crate::ecs::components::InteractionId;
+
+// The buyer can't pay the whole offer and asks the seller for the rest on
+// credit
+#[derive(Event, Debug)]
+pub struct CreditRequested {
+    pub id: InteractionId,
+    pub buyer: Entity,
+    pub seller: Entity,
+    pub quantity: usize,
+    pub price: usize,
+    // what the buyer can pay now
+    pub cash: usize,
+}
+
+#[derive(Event, Debug)]
+pub struct CreditExtended {
+    pub creditor: Entity,
+    pub debtor: Entity,
+    pub principal: usize,
+}
+
+// What the debtor owes the creditor now, for both of them to remember
+#[derive(Event, Debug)]
+pub struct DebtUpdated {
+    pub creditor: Entity,
+    pub debtor: Entity,
+    pub owed: usize,
+}
+
+#[derive(Event, Debug)]
+pub struct DebtRepaid {
+    pub creditor: Entity,
+    pub debtor: Entity,
+}
+
+#[derive(Event, Debug)]
+pub struct DebtDefaulted {
+    pub creditor: Entity,
+    pub debtor: Entity,
+    pub owed: usize,
+}
@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+use crate::ecs::{
+    credit::{resources::CreditConfig, systems::*},
+    game_state::GameState,
+};
+
+pub struct CreditPlugin;
+
+impl Plugin for CreditPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CreditConfig>()
+            .add_systems(
+                Update,
+                repay_debts_system.run_if(in_state(GameState::Running)),
+            )
+            .add_observer(seller_considers_credit)
+            .add_observer(record_debt)
+            .add_observer(remember_debt)
+            .add_observer(trust_paying_buyer)
+            .add_observer(trust_repaid_debtor)
+            .add_observer(distrust_defaulter);
+    }
+}
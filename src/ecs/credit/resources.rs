@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+
+#[derive(Resource, Debug, Clone)]
+pub struct CreditConfig {
+    // sellers only lend to buyers they trust at least this much, above the
+    // trust in strangers so that buyers first pay cash or repay debts
+    pub min_trust: f32,
+    // most a buyer may owe in total, interest included
+    pub max_debt: usize,
+    // added once to what is borrowed
+    pub interest: f32,
+    pub term_secs: f32,
+    pub repay_every_secs: f32,
+    // how the creditor's trust in the debtor moves
+    pub paid_trade_trust_gain: f32,
+    pub repaid_trust_gain: f32,
+    pub default_trust_loss: f32,
+}
+
+impl Default for CreditConfig {
+    fn default() -> Self {
+        Self {
+            min_trust: 0.85,
+            max_debt: 15,
+            interest: 0.2,
+            term_secs: 180.,
+            repay_every_secs: 2.,
+            paid_trade_trust_gain: 0.02,
+            repaid_trust_gain: 0.05,
+            default_trust_loss: 0.3,
+        }
+    }
+}
+
+impl CreditConfig {
+    pub fn owed_for(&self, principal: usize) -> usize {
+        principal + (principal as f32 * self.interest).ceil() as usize
+    }
+}
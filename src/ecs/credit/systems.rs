@@ -0,0 +1,284 @@
+use bevy::prelude::*;
+
+use crate::{
+    core::item::ItemEnum,
+    ecs::{
+        agent::Agent,
+        credit::{
+            components::{Debts, Iou},
+            events::{CreditExtended, CreditRequested, DebtDefaulted, DebtRepaid, DebtUpdated},
+            resources::CreditConfig,
+        },
+        interaction::common::{
+            components::{InteractionEndReason, RejectionReason},
+            events::InteractionEnded,
+        },
+        knowledge::{AgentKnowledge, KnowledgeConfig, KnowledgeFact, SharedKnowledge},
+        logs::AddLogEntry,
+        trade::{
+            components::{TradeNegotiation, TradeRole},
+            events::OfferAgreed,
+            systems::accept_offer,
+        },
+    },
+};
+
+pub fn seller_considers_credit(
+    trigger: Trigger<CreditRequested>,
+    knowledge_query: Query<&AgentKnowledge>,
+    debts_query: Query<&Debts>,
+    config: Res<CreditConfig>,
+    knowledge_config: Res<KnowledgeConfig>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+    mut commands: Commands,
+) {
+    let request = trigger.event();
+    let credit = request.price.saturating_sub(request.cash);
+
+    let trust = knowledge_query.get(request.seller).map_or(0., |knowledge| {
+        knowledge.trust_in(request.buyer, &knowledge_config)
+    });
+    let owed = debts_query.get(request.buyer).map_or(0, Debts::total_owed);
+
+    if trust >= config.min_trust && owed + config.owed_for(credit) <= config.max_debt {
+        add_log_writer.send(AddLogEntry::new(
+            request.seller,
+            format!("Sold to {} with {} on credit", request.buyer, credit).as_str(),
+        ));
+        accept_offer(
+            &mut commands,
+            request.id,
+            request.buyer,
+            request.seller,
+            request.quantity,
+            request.price,
+            credit,
+        );
+        return;
+    }
+
+    add_log_writer.send(AddLogEntry::new(
+        request.seller,
+        format!(
+            "Refused credit to {} (trust {:.2}, owes {})",
+            request.buyer, trust, owed
+        )
+        .as_str(),
+    ));
+    commands.trigger(InteractionEnded {
+        id: request.id,
+        source: request.buyer,
+        target: request.seller,
+        reason: InteractionEndReason::Rejected(RejectionReason::Declined),
+    });
+}
+
+pub fn record_debt(
+    trigger: Trigger<CreditExtended>,
+    mut debts_query: Query<&mut Debts>,
+    config: Res<CreditConfig>,
+    time: Res<Time>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+    mut commands: Commands,
+) {
+    let Ok(mut debts) = debts_query.get_mut(trigger.debtor) else {
+        warn!("No debts to record a credit on for {}", trigger.debtor);
+        return;
+    };
+
+    let iou = Iou {
+        creditor: trigger.creditor,
+        principal: trigger.principal,
+        owed: config.owed_for(trigger.principal),
+        due_at: time.elapsed_secs() + config.term_secs,
+    };
+    add_log_writer.send(AddLogEntry::new(
+        trigger.debtor,
+        format!(
+            "Owes {} to {} by {:.0}s",
+            iou.owed, iou.creditor, iou.due_at
+        )
+        .as_str(),
+    ));
+    debts.add(iou);
+    commands.trigger(DebtUpdated {
+        creditor: trigger.creditor,
+        debtor: trigger.debtor,
+        owed: debts.owed_to(trigger.creditor),
+    });
+}
+
+// Both sides know the debt as a fact, which may then spread like any other
+pub fn remember_debt(
+    trigger: Trigger<DebtUpdated>,
+    mut knowledge_query: Query<&mut AgentKnowledge>,
+    mut shared_knowledge: ResMut<SharedKnowledge>,
+    knowledge_config: Res<KnowledgeConfig>,
+) {
+    for entity in [trigger.creditor, trigger.debtor] {
+        if let Ok(mut knowledge) = knowledge_query.get_mut(entity) {
+            knowledge.witness(
+                KnowledgeFact::Debt {
+                    debtor: trigger.debtor,
+                    creditor: trigger.creditor,
+                    amount: trigger.owed,
+                },
+                &mut shared_knowledge,
+                &knowledge_config,
+            );
+        }
+    }
+}
+
+// Debtors pay back whatever money they hold, what is still owed past its due
+// date is a default. Debts to creditors no longer around are dropped.
+pub fn repay_debts_system(
+    mut debts_query: Query<(Entity, &mut Debts)>,
+    mut agent_query: Query<&mut Agent>,
+    config: Res<CreditConfig>,
+    time: Res<Time>,
+    mut since_last_check: Local<f32>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+    mut commands: Commands,
+) {
+    *since_last_check += time.delta_secs();
+    if *since_last_check < config.repay_every_secs {
+        return;
+    }
+    *since_last_check = 0.;
+
+    for (debtor, mut debts) in &mut debts_query {
+        let Ok(agent) = agent_query.get(debtor) else {
+            continue;
+        };
+        let money = agent.inventory.get_qty(ItemEnum::MONEY);
+
+        let gone: Vec<Entity> = debts
+            .ious()
+            .map(|iou| iou.creditor)
+            .filter(|creditor| !agent_query.contains(*creditor))
+            .collect();
+        for creditor in gone {
+            let owed = debts.write_off(creditor);
+            if owed == 0 {
+                continue;
+            }
+            add_log_writer.send(AddLogEntry::new(
+                debtor,
+                format!("No one to repay {} to, {} is gone", owed, creditor).as_str(),
+            ));
+            commands.trigger(DebtUpdated {
+                creditor,
+                debtor,
+                owed: 0,
+            });
+        }
+
+        let payments = debts.repay(money, |creditor, amount| {
+            let Ok([mut debtor_agent, mut creditor_agent]) =
+                agent_query.get_many_mut([debtor, creditor])
+            else {
+                return false;
+            };
+            let paid = debtor_agent
+                .inventory
+                .remove(ItemEnum::MONEY, amount)
+                .is_ok();
+            if paid {
+                creditor_agent.inventory.add(ItemEnum::MONEY, amount);
+            }
+            paid
+        });
+
+        for (creditor, amount, settled) in payments {
+            add_log_writer.send(AddLogEntry::new(
+                debtor,
+                format!("Repaid {} to {}", amount, creditor).as_str(),
+            ));
+            add_log_writer.send(AddLogEntry::new(
+                creditor,
+                format!("Got {} back from {}", amount, debtor).as_str(),
+            ));
+            commands.trigger(DebtUpdated {
+                creditor,
+                debtor,
+                owed: debts.owed_to(creditor),
+            });
+            if settled {
+                commands.trigger(DebtRepaid { creditor, debtor });
+            }
+        }
+
+        for iou in debts.take_overdue(time.elapsed_secs()) {
+            commands.trigger(DebtUpdated {
+                creditor: iou.creditor,
+                debtor,
+                owed: debts.owed_to(iou.creditor),
+            });
+            commands.trigger(DebtDefaulted {
+                creditor: iou.creditor,
+                debtor,
+                owed: iou.owed,
+            });
+        }
+    }
+}
+
+// Buyers paying cash in full earn a little of the seller's trust
+pub fn trust_paying_buyer(
+    trigger: Trigger<OfferAgreed>,
+    mut query: Query<(&mut AgentKnowledge, &TradeNegotiation)>,
+    config: Res<CreditConfig>,
+    knowledge_config: Res<KnowledgeConfig>,
+) {
+    if trigger.credit > 0 {
+        return;
+    }
+
+    if let Ok((mut knowledge, trade)) = query.get_mut(trigger.target) {
+        if trade.role == TradeRole::Seller {
+            knowledge.change_trust(
+                trade.partner,
+                config.paid_trade_trust_gain,
+                &knowledge_config,
+            );
+        }
+    }
+}
+
+pub fn trust_repaid_debtor(
+    trigger: Trigger<DebtRepaid>,
+    mut knowledge_query: Query<&mut AgentKnowledge>,
+    config: Res<CreditConfig>,
+    knowledge_config: Res<KnowledgeConfig>,
+) {
+    if let Ok(mut knowledge) = knowledge_query.get_mut(trigger.creditor) {
+        knowledge.change_trust(trigger.debtor, config.repaid_trust_gain, &knowledge_config);
+    }
+}
+
+// The debt is written off, the creditor won't forget it
+pub fn distrust_defaulter(
+    trigger: Trigger<DebtDefaulted>,
+    mut knowledge_query: Query<&mut AgentKnowledge>,
+    config: Res<CreditConfig>,
+    knowledge_config: Res<KnowledgeConfig>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+) {
+    if let Ok(mut knowledge) = knowledge_query.get_mut(trigger.creditor) {
+        knowledge.change_trust(
+            trigger.debtor,
+            -config.default_trust_loss,
+            &knowledge_config,
+        );
+    }
+
+    add_log_writer.send(AddLogEntry::new(
+        trigger.debtor,
+        format!("Defaulted on {} owed to {}", trigger.owed, trigger.creditor).as_str(),
+    ));
+    add_log_writer.send(AddLogEntry::new(
+        trigger.creditor,
+        format!("{} defaulted on {} owed", trigger.debtor, trigger.owed).as_str(),
+    ));
+}
@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+
+// Buyers lined up at a seller, in arrival order. The first ones stand
+// closest to the stall.
+#[derive(Component, Debug, Default)]
+pub struct SellerQueue {
+    buyers: Vec<Entity>,
+}
+
+impl SellerQueue {
+    // Slot of the buyer, at the back of the queue when new
+    pub fn join(&mut self, buyer: Entity) -> usize {
+        match self.buyers.iter().position(|v| *v == buyer) {
+            Some(slot) => slot,
+            None => {
+                self.buyers.push(buyer);
+                self.buyers.len() - 1
+            }
+        }
+    }
+
+    pub fn leave(&mut self, buyer: &Entity) {
+        self.buyers.retain(|v| v != buyer);
+    }
+
+    pub fn buyers(&self) -> impl Iterator<Item = (usize, Entity)> + '_ {
+        self.buyers.iter().copied().enumerate()
+    }
+}
+
+// Where a buyer stands in a seller queue
+#[derive(Component, Debug)]
+pub struct QueuedAt {
+    pub seller: Entity,
+    pub slot: usize,
+    pub position: Vec3,
+    // close enough to the seller to trade from the slot, otherwise the buyer
+    // waits until it moves up
+    pub within_reach: bool,
+}
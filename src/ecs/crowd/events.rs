@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+
+// The buyer takes a place in the seller queue, or keeps its own, and walks
+// to it
+#[derive(Event, Debug)]
+pub struct JoinSellerQueue {
+    pub buyer: Entity,
+    pub seller: Entity,
+}
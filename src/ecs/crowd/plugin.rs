@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+use crate::ecs::{
+    crowd::{resources::CrowdConfig, systems::*},
+    game_state::GameState,
+};
+
+pub struct CrowdPlugin;
+
+impl Plugin for CrowdPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CrowdConfig>()
+            .add_systems(
+                Update,
+                (queue_positions_system, separation_system).run_if(in_state(GameState::Running)),
+            )
+            .add_observer(join_seller_queue)
+            .add_observer(leave_seller_queue);
+    }
+}
@@ -0,0 +1,50 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+#[derive(Resource, Debug, Clone)]
+pub struct CrowdConfig {
+    // agents closer than twice this are pushed apart
+    pub agent_radius: f32,
+    // how fast overlapping agents are pushed apart, per second
+    pub separation_speed: f32,
+}
+
+impl Default for CrowdConfig {
+    fn default() -> Self {
+        Self {
+            agent_radius: 10.,
+            separation_speed: 80.,
+        }
+    }
+}
+
+impl CrowdConfig {
+    // Distance between queue rings. The first two rings stay within
+    // interaction reach of the seller, with an agent radius to spare for
+    // arriving around the slot.
+    pub fn queue_spacing(&self, interaction_radius: f32) -> f32 {
+        ((interaction_radius - self.agent_radius) / 2.).max(self.agent_radius * 2.)
+    }
+
+    // Queue slots go around the seller in rings, 6 in the first one, 12 in
+    // the second and so on
+    pub fn queue_slot_offset(&self, slot: usize, interaction_radius: f32) -> Vec3 {
+        let mut ring = 1;
+        let mut first = 0;
+        while slot >= first + 6 * ring {
+            first += 6 * ring;
+            ring += 1;
+        }
+
+        let angle = (slot - first) as f32 / (6 * ring) as f32 * TAU;
+        let radius = ring as f32 * self.queue_spacing(interaction_radius);
+        Vec3::new(angle.cos(), angle.sin(), 0.) * radius
+    }
+
+    // Whether a buyer standing at the slot can trade with the seller
+    pub fn slot_within_reach(&self, slot: usize, interaction_radius: f32) -> bool {
+        self.queue_slot_offset(slot, interaction_radius).length() + self.agent_radius
+            <= interaction_radius
+    }
+}
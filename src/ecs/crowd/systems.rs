@@ -0,0 +1,157 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::ecs::{
+    agent::Agent,
+    buy::tasks::components::BuyTask,
+    components::{Interacting, Walking},
+    crowd::{
+        components::{QueuedAt, SellerQueue},
+        events::JoinSellerQueue,
+        resources::CrowdConfig,
+    },
+    logs::AddLogEntry,
+    map::resources::WorldMap,
+    movement::resources::MovementConfig,
+    roles::seller::SellerRole,
+    spatial::SpatialGrid,
+};
+
+pub fn join_seller_queue(
+    trigger: Trigger<JoinSellerQueue>,
+    mut queue_query: Query<(&SellerRole, &mut SellerQueue)>,
+    buyer_query: Query<(&Transform, Option<&QueuedAt>)>,
+    config: Res<CrowdConfig>,
+    movement_config: Res<MovementConfig>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+    mut commands: Commands,
+) {
+    let Ok((transform, queued)) = buyer_query.get(trigger.buyer) else {
+        return;
+    };
+
+    if let Some(queued) = queued.filter(|v| v.seller != trigger.seller) {
+        if let Ok((_, mut old_queue)) = queue_query.get_mut(queued.seller) {
+            old_queue.leave(&trigger.buyer);
+        }
+    }
+
+    let Ok((seller_role, mut queue)) = queue_query.get_mut(trigger.seller) else {
+        return;
+    };
+
+    let slot = queue.join(trigger.buyer);
+    let reach = movement_config.interaction_radius;
+    let position = seller_role.location + config.queue_slot_offset(slot, reach);
+
+    commands.entity(trigger.buyer).insert(QueuedAt {
+        seller: trigger.seller,
+        slot,
+        position,
+        within_reach: config.slot_within_reach(slot, reach),
+    });
+
+    if transform.translation.distance(position) > config.agent_radius {
+        add_log_writer.send(AddLogEntry::new(
+            trigger.buyer,
+            format!("Starting Walking to the seller queue, slot {}", slot).as_str(),
+        ));
+        commands
+            .entity(trigger.buyer)
+            .insert(Walking::new_without_idle(position).arriving_within(config.agent_radius));
+    }
+}
+
+pub fn leave_seller_queue(
+    trigger: Trigger<OnRemove, BuyTask>,
+    buyer_query: Query<&QueuedAt>,
+    mut queue_query: Query<&mut SellerQueue>,
+    mut commands: Commands,
+) {
+    let Ok(queued) = buyer_query.get(trigger.entity()) else {
+        return;
+    };
+
+    if let Ok(mut queue) = queue_query.get_mut(queued.seller) {
+        queue.leave(&trigger.entity());
+    }
+
+    commands.entity(trigger.entity()).remove::<QueuedAt>();
+}
+
+// Buyers move up when the ones before them leave the queue. Those already
+// talking to the seller stay where they are.
+pub fn queue_positions_system(
+    queue_query: Query<(&SellerRole, &SellerQueue)>,
+    mut buyer_query: Query<(&mut QueuedAt, Has<Interacting>)>,
+    config: Res<CrowdConfig>,
+    movement_config: Res<MovementConfig>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+    mut commands: Commands,
+) {
+    let reach = movement_config.interaction_radius;
+    for (seller_role, queue) in &queue_query {
+        for (slot, buyer) in queue.buyers() {
+            let Ok((mut queued, interacting)) = buyer_query.get_mut(buyer) else {
+                continue;
+            };
+            if queued.slot == slot || interacting {
+                continue;
+            }
+
+            queued.slot = slot;
+            queued.position = seller_role.location + config.queue_slot_offset(slot, reach);
+            queued.within_reach = config.slot_within_reach(slot, reach);
+
+            add_log_writer.send(AddLogEntry::new(
+                buyer,
+                format!("Moving up to slot {} of the seller queue", slot).as_str(),
+            ));
+            commands.entity(buyer).insert(
+                Walking::new_without_idle(queued.position).arriving_within(config.agent_radius),
+            );
+        }
+    }
+}
+
+// Agents closer than two radii push each other apart, never onto an
+// impassable tile
+pub fn separation_system(
+    mut query: Query<(Entity, &mut Transform), With<Agent>>,
+    grid: Res<SpatialGrid>,
+    map: Res<WorldMap>,
+    config: Res<CrowdConfig>,
+    time: Res<Time>,
+) {
+    let min_distance = config.agent_radius * 2.;
+    let max_push = config.separation_speed * time.delta_secs();
+
+    query.par_iter_mut().for_each(|(entity, mut transform)| {
+        let position = transform.translation.truncate();
+
+        let push: Vec2 = grid
+            .positions_within(transform.translation, min_distance)
+            .filter(|(other, _)| *other != entity)
+            .map(|(_, other_position)| {
+                let away = position - other_position;
+                let overlap = min_distance - away.length();
+                // Agents on the same spot split in a direction of their own
+                let direction = away.try_normalize().unwrap_or_else(|| {
+                    let angle = entity.index() as f32 * 0.618 * TAU;
+                    Vec2::new(angle.cos(), angle.sin())
+                });
+                direction * overlap / 2.
+            })
+            .sum();
+
+        if push == Vec2::ZERO {
+            return;
+        }
+
+        let to = transform.translation + push.clamp_length_max(max_push).extend(0.);
+        if map.is_passable(to) {
+            transform.translation = to;
+        }
+    });
+}
@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use crate::{core::item::ItemEnum, ecs::action::components::*};
+
+// Selling personal goods to a seller of them, standing next to it
+#[derive(Component)]
+pub struct SellingBack {
+    pub seller: Entity,
+    pub item: ItemEnum,
+    pub qty: usize,
+    lifecycle: ActionLifecycle,
+}
+
+impl SellingBack {
+    pub fn new(seller: Entity, item: ItemEnum, qty: usize) -> Self {
+        Self {
+            seller,
+            item,
+            qty,
+            lifecycle: ActionLifecycle::new()
+                .timeout_after(10.)
+                .idle_at_completion(),
+        }
+    }
+}
+
+impl Action for SellingBack {
+    const KIND: ActionKind = ActionKind::SellBack;
+
+    fn lifecycle(&self) -> &ActionLifecycle {
+        &self.lifecycle
+    }
+    fn lifecycle_mut(&mut self) -> &mut ActionLifecycle {
+        &mut self.lifecycle
+    }
+}
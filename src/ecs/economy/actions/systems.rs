@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+
+use crate::{
+    core::item::ItemEnum,
+    ecs::{
+        action::components::{Action, ActionFailure},
+        agent::Agent,
+        economy::{actions::components::SellingBack, resources::MoneySupplyConfig},
+        logs::AddLogEntry,
+        movement::resources::MovementConfig,
+    },
+};
+
+// The seller takes as many units as it can pay for, as long as it is still
+// within reach
+pub fn handle_selling_back_action(
+    mut query: Query<(Entity, &mut SellingBack)>,
+    mut agent_query: Query<&mut Agent>,
+    transform_query: Query<&Transform>,
+    config: Res<MoneySupplyConfig>,
+    movement_config: Res<MovementConfig>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+) {
+    for (entity, mut selling_back) in &mut query {
+        if !selling_back.lifecycle().is_in_progress() {
+            continue;
+        }
+
+        let Ok([mut agent, mut seller]) = agent_query.get_many_mut([entity, selling_back.seller])
+        else {
+            selling_back
+                .lifecycle_mut()
+                .fail(ActionFailure::TargetNotFound);
+            continue;
+        };
+
+        let Ok([transform, seller_transform]) =
+            transform_query.get_many([entity, selling_back.seller])
+        else {
+            selling_back
+                .lifecycle_mut()
+                .fail(ActionFailure::TargetNotFound);
+            continue;
+        };
+        if transform.translation.distance(seller_transform.translation)
+            > movement_config.interaction_radius
+        {
+            add_log_writer.send(AddLogEntry::new(
+                entity,
+                "Sell back -> the seller is out of reach",
+            ));
+            selling_back
+                .lifecycle_mut()
+                .fail(ActionFailure::InteractionFailed);
+            continue;
+        }
+
+        let price = config.sell_back_unit_price.max(1);
+        let qty = selling_back
+            .qty
+            .min(agent.inventory.get_qty(selling_back.item))
+            .min(seller.inventory.get_qty(ItemEnum::MONEY) / price);
+
+        if qty == 0 {
+            add_log_writer.send(AddLogEntry::new(
+                entity,
+                "Sell back -> the seller can't buy anything",
+            ));
+            selling_back
+                .lifecycle_mut()
+                .fail(ActionFailure::InteractionFailed);
+            continue;
+        }
+
+        // both have enough, qty is capped by what they hold
+        if let (Ok(_), Ok(_)) = (
+            agent.inventory.remove(selling_back.item, qty),
+            seller.inventory.remove(ItemEnum::MONEY, qty * price),
+        ) {
+            agent.inventory.add(ItemEnum::MONEY, qty * price);
+            seller.inventory.add(selling_back.item, qty);
+        }
+
+        add_log_writer.send(AddLogEntry::new(
+            entity,
+            format!(
+                "Sell back -> sold {} {:?} for {}",
+                qty,
+                selling_back.item,
+                qty * price
+            )
+            .as_str(),
+        ));
+        selling_back.lifecycle_mut().complete();
+    }
+}
@@ -0,0 +1,4 @@
+pub mod actions;
+pub mod plugin;
+pub mod resources;
+mod systems;
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+use crate::ecs::{
+    action::plugin::RegisterAction,
+    economy::{
+        actions::{components::SellingBack, systems::handle_selling_back_action},
+        resources::{MoneyStats, MoneySupplyConfig},
+        systems::*,
+    },
+    employment::systems::handle_idle_job_seekers,
+    game_state::GameState,
+    roles::none::handle_idle_none_role,
+};
+
+pub struct EconomyPlugin;
+
+impl Plugin for EconomyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MoneySupplyConfig>()
+            .init_resource::<MoneyStats>()
+            .register_action::<SellingBack>()
+            .add_systems(
+                Update,
+                (
+                    stipend_system,
+                    money_stats_system,
+                    handle_selling_back_action,
+                    // a job first, then anything else before wandering
+                    (handle_idle_sellers_back, handle_idle_foragers)
+                        .run_if(|config: Res<MoneySupplyConfig>| config.sell_back)
+                        .after(handle_idle_job_seekers)
+                        .before(handle_idle_none_role),
+                )
+                    .run_if(in_state(GameState::Running)),
+            );
+    }
+}
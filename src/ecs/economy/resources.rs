@@ -0,0 +1,80 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+// Where money comes from. Trades and wages only move it around, the
+// stipend is the only source of new money.
+#[derive(Resource, Debug, Clone)]
+pub struct MoneySupplyConfig {
+    // unemployed agents short of money look for a job
+    pub job_income: bool,
+    // paid to every agent, 0 to disable
+    pub stipend: usize,
+    pub stipend_every_secs: f32,
+    // agents short of money forage and sell what they don't need back to
+    // sellers of it
+    pub sell_back: bool,
+    pub sell_back_unit_price: usize,
+    pub forage_amount: usize,
+    // slower than producers, who know the trade
+    pub forage_secs_per_unit: f32,
+    // units of each item kept for themselves
+    pub keep_units: usize,
+    // agents with less money than this go looking for income
+    pub short_of_money_below: usize,
+    pub stats_every_secs: f32,
+    pub stats_history: usize,
+}
+
+impl Default for MoneySupplyConfig {
+    fn default() -> Self {
+        Self {
+            job_income: true,
+            stipend: 3,
+            stipend_every_secs: 60.,
+            sell_back: true,
+            sell_back_unit_price: 2,
+            forage_amount: 3,
+            forage_secs_per_unit: 2.,
+            keep_units: 1,
+            short_of_money_below: 10,
+            stats_every_secs: 10.,
+            stats_history: 60,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MoneySample {
+    pub secs: f32,
+    pub total: usize,
+    pub held_by_sellers: usize,
+    // part of the total collected as taxes
+    pub treasury: usize,
+    // agents without any money
+    pub broke: usize,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct MoneyStats {
+    // money created since the start (stipends)
+    pub minted: usize,
+    samples: VecDeque<MoneySample>,
+}
+
+impl MoneyStats {
+    pub fn record(&mut self, sample: MoneySample, history: usize) {
+        self.samples.push_back(sample);
+        while self.samples.len() > history {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn latest(&self) -> Option<&MoneySample> {
+        self.samples.back()
+    }
+
+    pub fn samples(&self) -> impl DoubleEndedIterator<Item = &MoneySample> {
+        self.samples.iter()
+    }
+}
@@ -0,0 +1,200 @@
+use bevy::prelude::*;
+
+use crate::{
+    core::item::ItemEnum,
+    ecs::{
+        agent::Agent,
+        components::{Idle, Walking},
+        economy::{
+            actions::components::SellingBack,
+            resources::{MoneySample, MoneyStats, MoneySupplyConfig},
+        },
+        employment::components::Employee,
+        government::components::Treasury,
+        harvest::{actions::components::Harvesting, components::ResourceNode},
+        logs::AddLogEntry,
+        movement::resources::MovementConfig,
+        roles::{none::NoneRole, seller::SellerRole},
+    },
+};
+
+pub fn stipend_system(
+    mut query: Query<&mut Agent>,
+    config: Res<MoneySupplyConfig>,
+    mut stats: ResMut<MoneyStats>,
+    time: Res<Time>,
+    mut since_last_stipend: Local<f32>,
+) {
+    *since_last_stipend += time.delta_secs();
+    if config.stipend == 0 || *since_last_stipend < config.stipend_every_secs {
+        return;
+    }
+    *since_last_stipend = 0.;
+
+    for mut agent in &mut query {
+        agent.inventory.add(ItemEnum::MONEY, config.stipend);
+        stats.minted += config.stipend;
+    }
+}
+
+pub fn money_stats_system(
+    query: Query<(&Agent, Has<SellerRole>)>,
+    treasury_query: Query<&Treasury>,
+    config: Res<MoneySupplyConfig>,
+    mut stats: ResMut<MoneyStats>,
+    time: Res<Time>,
+    mut since_last_sample: Local<f32>,
+) {
+    *since_last_sample += time.delta_secs();
+    if *since_last_sample < config.stats_every_secs {
+        return;
+    }
+    *since_last_sample = 0.;
+
+    let treasury = treasury_query.get_single().map_or(0, Treasury::funds);
+    let mut sample = MoneySample {
+        secs: time.elapsed_secs(),
+        total: treasury,
+        held_by_sellers: 0,
+        treasury,
+        broke: 0,
+    };
+
+    for (agent, is_seller) in &query {
+        let money = agent.inventory.get_qty(ItemEnum::MONEY);
+        sample.total += money;
+        if is_seller {
+            sample.held_by_sellers += money;
+        }
+        if money == 0 {
+            sample.broke += 1;
+        }
+    }
+
+    info!(
+        "Money supply -> {} in total, {} held by sellers, {} in the treasury, {} agents broke",
+        sample.total, sample.held_by_sellers, sample.treasury, sample.broke
+    );
+    stats.record(sample, config.stats_history);
+}
+
+// Some item the agent holds more of than it keeps for itself
+fn surplus_of(agent: &Agent, keep_units: usize) -> Option<(ItemEnum, usize)> {
+    ItemEnum::ALL
+        .into_iter()
+        .filter(|item| *item != ItemEnum::MONEY)
+        .map(|item| (item, agent.inventory.get_qty(item)))
+        .find(|(_, qty)| *qty > keep_units)
+        .map(|(item, qty)| (item, qty - keep_units))
+}
+
+// Unemployed agents short of money sell their surplus to the closest
+// seller of it
+pub fn handle_idle_sellers_back(
+    query: Query<(Entity, &Transform, &Agent), With<Idle>>,
+    role_query: Query<(Has<NoneRole>, Has<Employee>)>,
+    seller_query: Query<(Entity, &Transform, &SellerRole)>,
+    config: Res<MoneySupplyConfig>,
+    movement_config: Res<MovementConfig>,
+    mut commands: Commands,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+) {
+    for (entity, transform, agent) in &query {
+        if role_query.get(entity) != Ok((true, false))
+            || agent.inventory.get_qty(ItemEnum::MONEY) >= config.short_of_money_below
+        {
+            continue;
+        }
+
+        let Some((item, qty)) = surplus_of(agent, config.keep_units) else {
+            continue;
+        };
+
+        let position = transform.translation;
+        let Some((seller, seller_transform, _)) = seller_query
+            .iter()
+            .filter(|(_, _, seller_role)| seller_role.ware == item)
+            .min_by(|(_, a, _), (_, b, _)| {
+                a.translation
+                    .distance(position)
+                    .total_cmp(&b.translation.distance(position))
+            })
+        else {
+            continue;
+        };
+
+        if seller_transform.translation.distance(position) > movement_config.interaction_radius {
+            add_log_writer.send(AddLogEntry::new(
+                entity,
+                format!("Role -> Start Walking to sell {} {:?} back", qty, item).as_str(),
+            ));
+            commands
+                .entity(entity)
+                .insert(
+                    Walking::new(seller_transform.translation)
+                        .arriving_within(movement_config.interaction_radius),
+                )
+                .remove::<Idle>();
+        } else {
+            commands
+                .entity(entity)
+                .insert(SellingBack::new(seller, item, qty))
+                .remove::<Idle>();
+        }
+    }
+}
+
+// Unemployed agents short of money and with nothing to sell gather a
+// little from the closest resource node
+pub fn handle_idle_foragers(
+    query: Query<(Entity, &Transform, &Agent), With<Idle>>,
+    role_query: Query<(Has<NoneRole>, Has<Employee>)>,
+    node_query: Query<(Entity, &ResourceNode)>,
+    config: Res<MoneySupplyConfig>,
+    movement_config: Res<MovementConfig>,
+    mut commands: Commands,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+) {
+    for (entity, transform, agent) in &query {
+        if role_query.get(entity) != Ok((true, false))
+            || agent.inventory.get_qty(ItemEnum::MONEY) >= config.short_of_money_below
+            || surplus_of(agent, config.keep_units).is_some()
+        {
+            continue;
+        }
+
+        let position = transform.translation;
+        let Some((node_entity, node)) = node_query
+            .iter()
+            .filter(|(_, node)| !node.is_depleted())
+            .min_by(|(_, a), (_, b)| {
+                a.location
+                    .distance(position)
+                    .total_cmp(&b.location.distance(position))
+            })
+        else {
+            continue;
+        };
+
+        if node.location.distance(position) > movement_config.arrival_radius {
+            add_log_writer.send(AddLogEntry::new(
+                entity,
+                format!("Role -> Start Walking to forage at the {:?}", node.kind).as_str(),
+            ));
+            commands
+                .entity(entity)
+                .insert(Walking::new(node.location))
+                .remove::<Idle>();
+        } else {
+            add_log_writer.send(AddLogEntry::new(entity, "Start Foraging"));
+            commands
+                .entity(entity)
+                .insert(Harvesting::new(
+                    node_entity,
+                    config.forage_amount + config.keep_units,
+                    config.forage_secs_per_unit,
+                ))
+                .remove::<Idle>();
+        }
+    }
+}
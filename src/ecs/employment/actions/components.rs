@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+
+use crate::ecs::{action::components::*, components::InteractionId};
+
+// Asking an employer for a job, through a hiring interaction
+#[derive(Component)]
+pub struct Applying {
+    pub employer: Entity,
+    pub interaction_id: Option<InteractionId>,
+    lifecycle: ActionLifecycle,
+}
+
+impl Applying {
+    pub fn new(employer: Entity) -> Self {
+        Self {
+            employer,
+            interaction_id: None,
+            lifecycle: ActionLifecycle::new()
+                .timeout_after(30.)
+                .idle_at_completion(),
+        }
+    }
+}
+
+impl Action for Applying {
+    const KIND: ActionKind = ActionKind::Apply;
+
+    fn lifecycle(&self) -> &ActionLifecycle {
+        &self.lifecycle
+    }
+    fn lifecycle_mut(&mut self) -> &mut ActionLifecycle {
+        &mut self.lifecycle
+    }
+}
+
+// A shift at the employer's resource node, the output goes to the employer
+#[derive(Component)]
+pub struct Working {
+    pub employer: Entity,
+    pub node: Entity,
+    lifecycle: ActionLifecycle,
+}
+
+impl Working {
+    pub fn new(employer: Entity, node: Entity, shift_secs: f32) -> Self {
+        Self {
+            employer,
+            node,
+            lifecycle: ActionLifecycle::with_duration(shift_secs).idle_at_completion(),
+        }
+    }
+}
+
+impl Action for Working {
+    const KIND: ActionKind = ActionKind::Work;
+    const PAUSE_WHILE_INTERACTING: bool = true;
+
+    fn lifecycle(&self) -> &ActionLifecycle {
+        &self.lifecycle
+    }
+    fn lifecycle_mut(&mut self) -> &mut ActionLifecycle {
+        &mut self.lifecycle
+    }
+}
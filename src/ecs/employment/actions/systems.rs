@@ -0,0 +1,108 @@
+use bevy::prelude::*;
+
+use crate::ecs::{
+    action::components::{Action, ActionFailure},
+    agent::Agent,
+    components::{Interacting, WaitingInteraction},
+    employment::{
+        actions::components::{Applying, Working},
+        components::{Employer, JobApplication},
+        events::ShiftEnded,
+        resources::EmploymentConfig,
+    },
+    harvest::components::ResourceNode,
+    interaction::common::{
+        components::{AgentInteractionItem, AgentInteractionKind},
+        events::InteractionRequested,
+    },
+    logs::AddLogEntry,
+};
+
+pub fn handle_applying_action(
+    mut query: Query<(Entity, &mut Applying)>,
+    busy_query: Query<(Has<Interacting>, Has<WaitingInteraction>)>,
+    employer_query: Query<(), With<Employer>>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+    mut commands: Commands,
+) {
+    for (worker, mut applying) in &mut query {
+        if !applying.lifecycle().is_in_progress()
+            || applying.interaction_id.is_some()
+            || busy_query.get(worker) != Ok((false, false))
+        {
+            continue;
+        }
+
+        if !employer_query.contains(applying.employer) {
+            add_log_writer.send(AddLogEntry::new(
+                worker,
+                "Employer not found, ending Applying",
+            ));
+            applying.lifecycle_mut().fail(ActionFailure::TargetNotFound);
+            continue;
+        }
+
+        add_log_writer.send(AddLogEntry::new(worker, "Asking for a job"));
+
+        let waiting = WaitingInteraction::new(worker, applying.employer);
+        let interaction_id = waiting.id;
+
+        commands.entity(worker).insert(waiting);
+        commands.trigger(InteractionRequested {
+            source: worker,
+            target: applying.employer,
+            item: AgentInteractionItem {
+                id: interaction_id,
+                kind: AgentInteractionKind::Hire(JobApplication {
+                    employer: applying.employer,
+                    worker,
+                }),
+            },
+        });
+
+        applying.interaction_id = Some(interaction_id);
+    }
+}
+
+// The shift output goes straight into the employer's inventory, whatever
+// is left at the node
+pub fn handle_working_action(
+    mut query: Query<(Entity, &mut Working)>,
+    mut node_query: Query<&mut ResourceNode>,
+    mut employer_query: Query<&mut Agent, With<Employer>>,
+    config: Res<EmploymentConfig>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+    mut commands: Commands,
+) {
+    for (worker, mut working) in &mut query {
+        if !working.lifecycle().is_in_progress() || !working.lifecycle().has_elapsed() {
+            continue;
+        }
+
+        let Ok(mut employer) = employer_query.get_mut(working.employer) else {
+            working.lifecycle_mut().fail(ActionFailure::TargetNotFound);
+            continue;
+        };
+
+        if let Ok(mut node) = node_query.get_mut(working.node) {
+            let taken = node.take(config.output_per_shift);
+            employer.inventory.add(node.kind.item(), taken);
+
+            add_log_writer.send(AddLogEntry::new(
+                worker,
+                format!(
+                    "Work -> gathered {} {:?} for the employer",
+                    taken,
+                    node.kind.item()
+                )
+                .as_str(),
+            ));
+        }
+
+        working.lifecycle_mut().complete();
+        commands.trigger(ShiftEnded {
+            employer: working.employer,
+            worker,
+        });
+    }
+}
@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+
+// Producer paying workers to gather its ware
+#[derive(Component, Debug)]
+pub struct Employer {
+    pub max_workers: usize,
+    // paid per shift
+    pub wage: usize,
+    workers: Vec<Entity>,
+}
+
+impl Employer {
+    pub fn new(max_workers: usize, wage: usize) -> Self {
+        Self {
+            max_workers,
+            wage,
+            workers: vec![],
+        }
+    }
+
+    pub fn has_vacancy(&self) -> bool {
+        self.workers.len() < self.max_workers
+    }
+
+    pub fn hire(&mut self, worker: Entity) {
+        if !self.workers.contains(&worker) {
+            self.workers.push(worker);
+        }
+    }
+
+    pub fn dismiss(&mut self, worker: Entity) {
+        self.workers.retain(|v| *v != worker);
+    }
+
+    pub fn workers(&self) -> &[Entity] {
+        &self.workers
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Employee {
+    pub employer: Entity,
+    pub wage: usize,
+    pub shifts: usize,
+}
+
+impl Employee {
+    pub fn new(employer: Entity, wage: usize) -> Self {
+        Self {
+            employer,
+            wage,
+            shifts: 0,
+        }
+    }
+}
+
+// Marker of the hiring interaction, on both the applicant and the employer
+#[derive(Component, Debug, Clone)]
+pub struct JobApplication {
+    pub employer: Entity,
+    pub worker: Entity,
+}
@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+#[derive(Event, Debug)]
+pub struct Hired {
+    pub employer: Entity,
+    pub worker: Entity,
+    pub wage: usize,
+}
+
+// The worker is done with a shift and expects its wage
+#[derive(Event, Debug)]
+pub struct ShiftEnded {
+    pub employer: Entity,
+    pub worker: Entity,
+}
@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+
+use crate::ecs::{
+    action::plugin::RegisterAction,
+    economy::resources::MoneySupplyConfig,
+    employment::{
+        actions::{
+            components::{Applying, Working},
+            systems::{handle_applying_action, handle_working_action},
+        },
+        resources::EmploymentConfig,
+        systems::*,
+    },
+    game_state::GameState,
+    roles::none::handle_idle_none_role,
+};
+
+pub struct EmploymentPlugin;
+
+impl Plugin for EmploymentPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EmploymentConfig>()
+            .register_action::<Applying>()
+            .register_action::<Working>()
+            .add_systems(Update, attach_employer)
+            .add_systems(
+                Update,
+                (
+                    // they leave Idle before the NoneRole starts wandering
+                    (
+                        handle_idle_employees,
+                        handle_idle_job_seekers
+                            .run_if(|config: Res<MoneySupplyConfig>| config.job_income),
+                    )
+                        .before(handle_idle_none_role),
+                    handle_applying_action,
+                    handle_working_action,
+                )
+                    .run_if(in_state(GameState::Running)),
+            )
+            .add_observer(employer_considers_application)
+            .add_observer(handle_hired)
+            .add_observer(handle_interaction_ended)
+            .add_observer(pay_wage);
+    }
+}
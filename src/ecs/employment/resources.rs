@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+#[derive(Resource, Debug, Clone)]
+pub struct EmploymentConfig {
+    pub max_workers: usize,
+    pub wage: usize,
+    pub shift_secs: f32,
+    // units of the employer's ware gathered during a shift
+    pub output_per_shift: usize,
+    // unemployed agents with less money than this look for a job
+    pub seek_job_below: usize,
+}
+
+impl Default for EmploymentConfig {
+    fn default() -> Self {
+        Self {
+            max_workers: 2,
+            wage: 6,
+            shift_secs: 20.,
+            output_per_shift: 10,
+            seek_job_below: 40,
+        }
+    }
+}
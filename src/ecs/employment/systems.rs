@@ -0,0 +1,316 @@
+use bevy::prelude::*;
+
+use crate::{
+    core::item::ItemEnum,
+    ecs::{
+        action::components::{Action, ActionFailure},
+        agent::Agent,
+        components::{Idle, Interacting, WaitingInteraction, Walking},
+        employment::{
+            actions::components::{Applying, Working},
+            components::{Employee, Employer, JobApplication},
+            events::{Hired, ShiftEnded},
+            resources::EmploymentConfig,
+        },
+        government::{
+            events::{TaxCollected, TaxKind},
+            resources::GovernmentConfig,
+        },
+        harvest::components::ResourceNode,
+        interaction::common::{
+            components::{InteractionEndReason, RejectionReason},
+            events::{InteractionEnded, InteractionReady},
+        },
+        logs::AddLogEntry,
+        movement::resources::MovementConfig,
+        roles::{none::NoneRole, seller::SellerRole},
+    },
+};
+
+// Every producer can take a few workers
+pub fn attach_employer(
+    query: Query<Entity, (With<SellerRole>, Without<Employer>)>,
+    config: Res<EmploymentConfig>,
+    mut commands: Commands,
+) {
+    for entity in &query {
+        commands
+            .entity(entity)
+            .insert(Employer::new(config.max_workers, config.wage));
+    }
+}
+
+// Unemployed agents short of money go ask the closest employer with a
+// vacancy for a job. Runs before the NoneRole wandering gets them.
+pub fn handle_idle_job_seekers(
+    query: Query<(Entity, &Transform, &Agent), With<Idle>>,
+    role_query: Query<(Has<NoneRole>, Has<Employee>)>,
+    employer_query: Query<(Entity, &Transform, &Employer)>,
+    config: Res<EmploymentConfig>,
+    movement_config: Res<MovementConfig>,
+    mut commands: Commands,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+) {
+    for (entity, transform, agent) in &query {
+        if role_query.get(entity) != Ok((true, false))
+            || agent.inventory.get_qty(ItemEnum::MONEY) >= config.seek_job_below
+        {
+            continue;
+        }
+
+        let position = transform.translation;
+        let Some((employer, employer_transform, _)) = employer_query
+            .iter()
+            .filter(|(_, _, employer)| employer.has_vacancy())
+            .min_by(|(_, a, _), (_, b, _)| {
+                a.translation
+                    .distance(position)
+                    .total_cmp(&b.translation.distance(position))
+            })
+        else {
+            continue;
+        };
+
+        if employer_transform.translation.distance(position) > movement_config.interaction_radius {
+            add_log_writer.send(AddLogEntry::new(
+                entity,
+                "Role -> Looking for a job, start Walking to an employer",
+            ));
+            commands
+                .entity(entity)
+                .insert(
+                    Walking::new(employer_transform.translation)
+                        .arriving_within(movement_config.interaction_radius),
+                )
+                .remove::<Idle>();
+        } else {
+            commands
+                .entity(entity)
+                .insert(Applying::new(employer))
+                .remove::<Idle>();
+        }
+    }
+}
+
+// Workers go to the closest node holding their employer's ware and work a
+// shift there
+pub fn handle_idle_employees(
+    query: Query<(Entity, &Transform, &Employee), With<Idle>>,
+    employer_query: Query<&SellerRole>,
+    node_query: Query<(Entity, &ResourceNode)>,
+    config: Res<EmploymentConfig>,
+    movement_config: Res<MovementConfig>,
+    mut commands: Commands,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+) {
+    for (entity, transform, employee) in &query {
+        let Ok(seller_role) = employer_query.get(employee.employer) else {
+            continue;
+        };
+
+        let position = transform.translation;
+        let Some((node_entity, node)) = node_query
+            .iter()
+            .filter(|(_, node)| node.kind.item() == seller_role.ware && !node.is_depleted())
+            .min_by(|(_, a), (_, b)| {
+                a.location
+                    .distance(position)
+                    .total_cmp(&b.location.distance(position))
+            })
+        else {
+            continue;
+        };
+
+        if node.location.distance(position) > movement_config.arrival_radius {
+            add_log_writer.send(AddLogEntry::new(
+                entity,
+                format!("Role -> Start Walking to work at the {:?}", node.kind).as_str(),
+            ));
+            commands
+                .entity(entity)
+                .insert(Walking::new(node.location))
+                .remove::<Idle>();
+        } else {
+            add_log_writer.send(AddLogEntry::new(entity, "Start Working"));
+            commands
+                .entity(entity)
+                .insert(Working::new(
+                    employee.employer,
+                    node_entity,
+                    config.shift_secs,
+                ))
+                .remove::<Idle>();
+        }
+    }
+}
+
+// Both sides are ready: the employer hires the applicant if it still has a
+// vacancy and can pay at least one shift
+pub fn employer_considers_application(
+    trigger: Trigger<InteractionReady>,
+    mut employer_query: Query<(&Agent, &mut Employer, &JobApplication, &Interacting)>,
+    mut commands: Commands,
+) {
+    let Ok((agent, mut employer, application, interacting)) =
+        employer_query.get_mut(trigger.target)
+    else {
+        return;
+    };
+
+    if application.employer != trigger.target || interacting.id != trigger.id {
+        return;
+    }
+
+    let reason =
+        if employer.has_vacancy() && agent.inventory.get_qty(ItemEnum::MONEY) >= employer.wage {
+            employer.hire(application.worker);
+            commands.trigger(Hired {
+                employer: trigger.target,
+                worker: application.worker,
+                wage: employer.wage,
+            });
+            InteractionEndReason::Completed
+        } else {
+            InteractionEndReason::Rejected(RejectionReason::Declined)
+        };
+
+    commands.trigger(InteractionEnded {
+        id: trigger.id,
+        source: trigger.source,
+        target: trigger.target,
+        reason,
+    });
+}
+
+pub fn handle_hired(
+    trigger: Trigger<Hired>,
+    name_query: Query<&Name>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+    mut commands: Commands,
+) {
+    commands
+        .entity(trigger.worker)
+        .insert(Employee::new(trigger.employer, trigger.wage));
+
+    let name_of = |entity: Entity| {
+        name_query
+            .get(entity)
+            .map(|v| v.to_string())
+            .unwrap_or(entity.to_string())
+    };
+
+    add_log_writer.send(AddLogEntry::new(
+        trigger.worker,
+        format!(
+            "Hired by {} for {} per shift",
+            name_of(trigger.employer),
+            trigger.wage
+        )
+        .as_str(),
+    ));
+    add_log_writer.send(AddLogEntry::new(
+        trigger.employer,
+        format!("Hired {}", name_of(trigger.worker)).as_str(),
+    ));
+}
+
+pub fn handle_interaction_ended(
+    trigger: Trigger<InteractionEnded>,
+    agent_query: Query<(Entity, &Interacting), With<JobApplication>>,
+    mut applying_query: Query<&mut Applying>,
+    mut commands: Commands,
+) {
+    for entity in [trigger.source, trigger.target] {
+        let Ok((entity, interacting)) = agent_query.get(entity) else {
+            continue;
+        };
+
+        if trigger.id == interacting.id {
+            commands
+                .entity(entity)
+                .remove::<(Interacting, JobApplication)>();
+        }
+    }
+
+    // the applicant, whether the interaction started or not
+    if let Ok(mut applying) = applying_query.get_mut(trigger.source) {
+        if applying.interaction_id != Some(trigger.id) {
+            return;
+        }
+
+        commands
+            .entity(trigger.source)
+            .remove::<WaitingInteraction>();
+
+        if trigger.reason == InteractionEndReason::Completed {
+            applying.lifecycle_mut().complete();
+        } else {
+            applying
+                .lifecycle_mut()
+                .fail(ActionFailure::InteractionFailed);
+        }
+    }
+}
+
+// Wages are paid at the end of every shift. An employer who can't pay
+// loses the worker.
+pub fn pay_wage(
+    trigger: Trigger<ShiftEnded>,
+    mut agent_query: Query<&mut Agent>,
+    mut employee_query: Query<&mut Employee>,
+    mut employer_query: Query<&mut Employer>,
+    config: Res<GovernmentConfig>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+    mut commands: Commands,
+) {
+    let Ok(mut employee) = employee_query.get_mut(trigger.worker) else {
+        return;
+    };
+
+    let paid = match agent_query.get_many_mut([trigger.employer, trigger.worker]) {
+        Ok([mut employer, mut worker]) => employer
+            .inventory
+            .remove(ItemEnum::MONEY, employee.wage)
+            .ok()
+            .map(|_| {
+                let tax = config.tax_on(TaxKind::Income, employee.wage);
+                worker.inventory.add(ItemEnum::MONEY, employee.wage - tax);
+                tax
+            }),
+        Err(_) => None,
+    };
+
+    if let Some(tax) = paid {
+        employee.shifts += 1;
+        add_log_writer.send(AddLogEntry::new(
+            trigger.worker,
+            format!(
+                "Shift done, paid {} after {} of tax",
+                employee.wage - tax,
+                tax
+            )
+            .as_str(),
+        ));
+        if tax > 0 {
+            commands.trigger(TaxCollected {
+                payer: trigger.worker,
+                kind: TaxKind::Income,
+                amount: tax,
+            });
+        }
+        add_log_writer.send(AddLogEntry::new(
+            trigger.employer,
+            format!("Paid a wage of {}", employee.wage).as_str(),
+        ));
+        return;
+    }
+
+    add_log_writer.send(AddLogEntry::new(
+        trigger.worker,
+        "Shift done but not paid, quitting the job",
+    ));
+    commands.entity(trigger.worker).remove::<Employee>();
+    if let Ok(mut employer) = employer_query.get_mut(trigger.employer) {
+        employer.dismiss(trigger.worker);
+    }
+}
@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+
+// The public purse, taxes go in and public spending comes out of it
+#[derive(Component, Debug, Default)]
+pub struct Treasury {
+    funds: usize,
+}
+
+impl Treasury {
+    pub fn funds(&self) -> usize {
+        self.funds
+    }
+
+    pub fn deposit(&mut self, amount: usize) {
+        self.funds += amount;
+    }
+
+    // Spends only what is above `reserve`, returns whether it could
+    pub fn spend(&mut self, amount: usize, reserve: usize) -> bool {
+        if self.funds < amount + reserve {
+            return false;
+        }
+        self.funds -= amount;
+        true
+    }
+}
@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaxKind {
+    Sales,
+    Income,
+}
+
+// Already withheld from the payer, goes to the treasury
+#[derive(Event, Debug)]
+pub struct TaxCollected {
+    pub payer: Entity,
+    pub kind: TaxKind,
+    pub amount: usize,
+}
+
+// A new sim-day starts, the treasury spends what the previous one brought
+#[derive(Event, Debug)]
+pub struct NewDay {
+    pub day: u32,
+}
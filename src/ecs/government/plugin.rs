@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+use crate::ecs::{
+    game_state::GameState,
+    government::{
+        resources::{GovernmentConfig, TreasuryLedger},
+        systems::*,
+    },
+};
+
+pub struct GovernmentPlugin;
+
+impl Plugin for GovernmentPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GovernmentConfig>()
+            .init_resource::<TreasuryLedger>()
+            .add_systems(Startup, spawn_treasury)
+            .add_systems(Update, new_day_system.run_if(in_state(GameState::Running)))
+            .add_observer(collect_tax)
+            .add_observer(pay_welfare)
+            .add_observer(build_public_well);
+    }
+}
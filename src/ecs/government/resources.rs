@@ -0,0 +1,94 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::ecs::government::events::TaxKind;
+
+#[derive(Resource, Debug, Clone)]
+pub struct GovernmentConfig {
+    // share of what sellers earn from a trade
+    pub sales_tax: f32,
+    // share of every wage
+    pub income_tax: f32,
+    pub day_secs: f32,
+    // never spent
+    pub reserve: usize,
+    // paid each day to agents with less money than `welfare_below`
+    pub welfare: usize,
+    pub welfare_below: usize,
+    pub well_cost: usize,
+    pub max_public_wells: usize,
+    pub history_days: usize,
+}
+
+impl Default for GovernmentConfig {
+    fn default() -> Self {
+        Self {
+            sales_tax: 0.2,
+            income_tax: 0.15,
+            day_secs: 120.,
+            reserve: 20,
+            welfare: 4,
+            welfare_below: 5,
+            well_cost: 150,
+            max_public_wells: 3,
+            history_days: 30,
+        }
+    }
+}
+
+impl GovernmentConfig {
+    pub fn tax_on(&self, kind: TaxKind, amount: usize) -> usize {
+        let rate = match kind {
+            TaxKind::Sales => self.sales_tax,
+            TaxKind::Income => self.income_tax,
+        };
+        ((amount as f32 * rate).round() as usize).min(amount)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DayTotals {
+    pub day: u32,
+    pub sales_tax: usize,
+    pub income_tax: usize,
+    pub welfare: usize,
+    pub public_works: usize,
+    // funds when the day closed
+    pub funds: usize,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct TreasuryLedger {
+    pub today: DayTotals,
+    pub wells_built: usize,
+    days: VecDeque<DayTotals>,
+}
+
+impl TreasuryLedger {
+    pub fn collect(&mut self, kind: TaxKind, amount: usize) {
+        match kind {
+            TaxKind::Sales => self.today.sales_tax += amount,
+            TaxKind::Income => self.today.income_tax += amount,
+        }
+    }
+
+    // Closes the current day and returns the number of the new one
+    pub fn close_day(&mut self, funds: usize, history: usize) -> u32 {
+        self.today.funds = funds;
+        self.days.push_back(self.today);
+        while self.days.len() > history {
+            self.days.pop_front();
+        }
+
+        self.today = DayTotals {
+            day: self.today.day + 1,
+            ..default()
+        };
+        self.today.day
+    }
+
+    pub fn days(&self) -> impl DoubleEndedIterator<Item = &DayTotals> {
+        self.days.iter()
+    }
+}
@@ -0,0 +1,131 @@
+use bevy::prelude::*;
+
+use crate::{
+    core::item::ItemEnum,
+    ecs::{
+        agent::Agent,
+        building::{components::BuildingKind, utils::BuildingSite},
+        government::{
+            components::Treasury,
+            events::{NewDay, TaxCollected},
+            resources::{GovernmentConfig, TreasuryLedger},
+        },
+        logs::AddLogEntry,
+    },
+};
+
+pub fn spawn_treasury(mut commands: Commands) {
+    commands.spawn((Treasury::default(), Name::new("Treasury")));
+}
+
+pub fn collect_tax(
+    trigger: Trigger<TaxCollected>,
+    mut treasury_query: Query<&mut Treasury>,
+    mut ledger: ResMut<TreasuryLedger>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+) {
+    let Ok(mut treasury) = treasury_query.get_single_mut() else {
+        return;
+    };
+
+    treasury.deposit(trigger.amount);
+    ledger.collect(trigger.kind, trigger.amount);
+    add_log_writer.send(AddLogEntry::new(
+        trigger.payer,
+        format!("Paid {} of {:?} tax", trigger.amount, trigger.kind).as_str(),
+    ));
+}
+
+pub fn new_day_system(
+    treasury_query: Query<&Treasury>,
+    config: Res<GovernmentConfig>,
+    mut ledger: ResMut<TreasuryLedger>,
+    mut commands: Commands,
+    time: Res<Time>,
+    mut since_dawn: Local<f32>,
+) {
+    *since_dawn += time.delta_secs();
+    if *since_dawn < config.day_secs {
+        return;
+    }
+    *since_dawn = 0.;
+
+    let funds = treasury_query.get_single().map_or(0, Treasury::funds);
+    let today = ledger.today;
+    info!(
+        "Day {} -> {} sales tax, {} income tax, {} welfare, {} public works, {} in the treasury",
+        today.day, today.sales_tax, today.income_tax, today.welfare, today.public_works, funds
+    );
+
+    let day = ledger.close_day(funds, config.history_days);
+    commands.trigger(NewDay { day });
+}
+
+// Poorest first, as long as the treasury can afford it
+pub fn pay_welfare(
+    _trigger: Trigger<NewDay>,
+    mut treasury_query: Query<&mut Treasury>,
+    mut agent_query: Query<(Entity, &mut Agent)>,
+    config: Res<GovernmentConfig>,
+    mut ledger: ResMut<TreasuryLedger>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+) {
+    let Ok(mut treasury) = treasury_query.get_single_mut() else {
+        return;
+    };
+    if config.welfare == 0 {
+        return;
+    }
+
+    let mut poor = agent_query
+        .iter()
+        .map(|(entity, agent)| (entity, agent.inventory.get_qty(ItemEnum::MONEY)))
+        .filter(|(_, money)| *money < config.welfare_below)
+        .collect::<Vec<_>>();
+    poor.sort_by_key(|(_, money)| *money);
+
+    for (entity, _) in poor {
+        if !treasury.spend(config.welfare, config.reserve) {
+            break;
+        }
+        if let Ok((_, mut agent)) = agent_query.get_mut(entity) {
+            agent.inventory.add(ItemEnum::MONEY, config.welfare);
+        }
+        ledger.today.welfare += config.welfare;
+        add_log_writer.send(AddLogEntry::new(
+            entity,
+            format!("Received {} of welfare", config.welfare).as_str(),
+        ));
+    }
+}
+
+pub fn build_public_well(
+    trigger: Trigger<NewDay>,
+    mut treasury_query: Query<&mut Treasury>,
+    mut site: BuildingSite,
+    config: Res<GovernmentConfig>,
+    mut ledger: ResMut<TreasuryLedger>,
+    mut commands: Commands,
+) {
+    let Ok(mut treasury) = treasury_query.get_single_mut() else {
+        return;
+    };
+    if ledger.wells_built >= config.max_public_wells
+        || treasury.funds() < config.well_cost + config.reserve
+    {
+        return;
+    }
+
+    let Some((_, entrance)) = site.spawn(&mut commands, BuildingKind::Well) else {
+        warn!("No site left for a public well");
+        return;
+    };
+
+    treasury.spend(config.well_cost, config.reserve);
+    ledger.today.public_works += config.well_cost;
+    ledger.wells_built += 1;
+    info!(
+        "Day {}: the treasury built a well at {}",
+        trigger.day, entrance
+    );
+}
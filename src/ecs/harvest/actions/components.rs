@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+
+use crate::ecs::action::components::*;
+
+// Gathering from a resource node, standing at its location
+#[derive(Component)]
+pub struct Harvesting {
+    pub node: Entity,
+    pub amount: usize,
+    lifecycle: ActionLifecycle,
+}
+
+impl Harvesting {
+    pub fn new(node: Entity, amount: usize, secs_per_unit: f32) -> Self {
+        Self {
+            node,
+            amount,
+            lifecycle: ActionLifecycle::with_duration(secs_per_unit * amount as f32)
+                .idle_at_completion(),
+        }
+    }
+}
+
+impl Action for Harvesting {
+    const KIND: ActionKind = ActionKind::Harvest;
+    const PAUSE_WHILE_INTERACTING: bool = true;
+
+    fn lifecycle(&self) -> &ActionLifecycle {
+        &self.lifecycle
+    }
+    fn lifecycle_mut(&mut self) -> &mut ActionLifecycle {
+        &mut self.lifecycle
+    }
+}
@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+
+use crate::ecs::{
+    action::components::{Action, ActionFailure},
+    agent::Agent,
+    harvest::{actions::components::Harvesting, components::ResourceNode},
+    logs::AddLogEntry,
+};
+
+// Whatever the node still holds once the work is done, the action fails
+// when someone else emptied it in the meantime
+pub fn handle_harvesting_action(
+    mut query: Query<(Entity, &mut Agent, &mut Harvesting)>,
+    mut node_query: Query<&mut ResourceNode>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+) {
+    for (entity, mut agent, mut harvesting) in &mut query {
+        if !harvesting.lifecycle().is_in_progress() || !harvesting.lifecycle().has_elapsed() {
+            continue;
+        }
+
+        let Ok(mut node) = node_query.get_mut(harvesting.node) else {
+            harvesting
+                .lifecycle_mut()
+                .fail(ActionFailure::TargetNotFound);
+            continue;
+        };
+
+        let taken = node.take(harvesting.amount);
+        if taken == 0 {
+            add_log_writer.send(AddLogEntry::new(
+                entity,
+                format!("Harvest -> the {:?} is depleted", node.kind).as_str(),
+            ));
+            harvesting
+                .lifecycle_mut()
+                .fail(ActionFailure::TargetNotFound);
+            continue;
+        }
+
+        agent.inventory.add(node.kind.item(), taken);
+        add_log_writer.send(AddLogEntry::new(
+            entity,
+            format!(
+                "Harvest -> took {} {:?} from the {:?}, {} left",
+                taken,
+                node.kind.item(),
+                node.kind,
+                node.stock()
+            )
+            .as_str(),
+        ));
+        harvesting.lifecycle_mut().complete();
+    }
+}
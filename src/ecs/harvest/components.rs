@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+
+use crate::core::item::ItemEnum;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    Well,
+    HuntingGround,
+    FishingSpot,
+    Field,
+}
+
+impl NodeKind {
+    // Fish and crops are sold as meat until there are more kinds of food
+    pub fn item(&self) -> ItemEnum {
+        match self {
+            NodeKind::Well => ItemEnum::WATER,
+            NodeKind::HuntingGround | NodeKind::FishingSpot | NodeKind::Field => ItemEnum::MEAT,
+        }
+    }
+
+    pub fn max_stock(&self) -> f32 {
+        match self {
+            NodeKind::Well => 150.,
+            NodeKind::HuntingGround => 80.,
+            NodeKind::FishingSpot => 100.,
+            NodeKind::Field => 120.,
+        }
+    }
+
+    // Units coming back per second, up to max_stock
+    pub fn regen_per_sec(&self) -> f32 {
+        match self {
+            NodeKind::Well => 0.5,
+            NodeKind::HuntingGround => 0.2,
+            NodeKind::FishingSpot => 0.3,
+            NodeKind::Field => 0.15,
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            NodeKind::Well => Color::srgb(0.3, 0.55, 0.8),
+            NodeKind::HuntingGround => Color::srgb(0.55, 0.25, 0.2),
+            NodeKind::FishingSpot => Color::srgb(0.4, 0.75, 0.75),
+            NodeKind::Field => Color::srgb(0.85, 0.75, 0.3),
+        }
+    }
+}
+
+// Where producers get what they sell. The stock is finite and slowly
+// regenerates, harvesting faster than that depletes the node.
+#[derive(Component, Debug)]
+pub struct ResourceNode {
+    pub kind: NodeKind,
+    // where harvesters stand
+    pub location: Vec3,
+    pub max_stock: f32,
+    pub regen_per_sec: f32,
+    stock: f32,
+}
+
+impl ResourceNode {
+    pub fn new(kind: NodeKind, location: Vec3) -> Self {
+        Self {
+            kind,
+            location,
+            max_stock: kind.max_stock(),
+            regen_per_sec: kind.regen_per_sec(),
+            stock: kind.max_stock(),
+        }
+    }
+
+    pub fn stock(&self) -> usize {
+        self.stock as usize
+    }
+
+    pub fn fill_ratio(&self) -> f32 {
+        self.stock / self.max_stock
+    }
+
+    pub fn is_depleted(&self) -> bool {
+        self.stock() == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.stock >= self.max_stock
+    }
+
+    // Takes up to `amount` whole units, returns how many were taken
+    pub fn take(&mut self, amount: usize) -> usize {
+        let taken = amount.min(self.stock());
+        self.stock -= taken as f32;
+        taken
+    }
+
+    pub fn regenerate(&mut self, secs: f32) {
+        self.stock = (self.stock + self.regen_per_sec * secs).min(self.max_stock);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod actions;
+pub mod components;
+pub mod plugin;
+pub mod resources;
+mod systems;
@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+
+use crate::ecs::{
+    action::plugin::RegisterAction,
+    game_state::GameState,
+    harvest::{
+        actions::{components::Harvesting, systems::handle_harvesting_action},
+        resources::HarvestConfig,
+        systems::*,
+    },
+};
+
+pub struct HarvestPlugin;
+
+impl Plugin for HarvestPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HarvestConfig>()
+            .register_action::<Harvesting>()
+            .add_systems(PostStartup, spawn_resource_nodes)
+            .add_observer(make_well_a_resource_node)
+            .add_systems(
+                Update,
+                (
+                    handle_harvesting_action,
+                    regenerate_resource_nodes_system,
+                    update_resource_node_sprites,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Running)),
+            );
+    }
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+#[derive(Resource, Debug, Clone)]
+pub struct HarvestConfig {
+    // units gathered by one harvest
+    pub amount: usize,
+    pub secs_per_unit: f32,
+    // producers go harvesting when they have less than this to sell
+    pub restock_below: usize,
+    // natural nodes placed on the map, wells come with their building
+    pub hunting_grounds: usize,
+    pub fishing_spots: usize,
+    pub fields: usize,
+}
+
+impl Default for HarvestConfig {
+    fn default() -> Self {
+        Self {
+            amount: 10,
+            secs_per_unit: 1.,
+            restock_below: 5,
+            hunting_grounds: 3,
+            fishing_spots: 2,
+            fields: 2,
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+
+use crate::ecs::{
+    building::components::{Building, BuildingKind},
+    harvest::{
+        components::{NodeKind, ResourceNode},
+        resources::HarvestConfig,
+    },
+    knowledge::{KnowledgeFact, SharedKnowledge},
+    map::{resources::WorldMap, terrain::Terrain},
+};
+
+// Wells are nodes, whether placed at startup or built later on
+pub fn make_well_a_resource_node(
+    trigger: Trigger<OnAdd, Building>,
+    building_query: Query<&Building>,
+    mut shared_knowledge: ResMut<SharedKnowledge>,
+    mut commands: Commands,
+) {
+    let Ok(building) = building_query.get(trigger.entity()) else {
+        return;
+    };
+    if building.kind != BuildingKind::Well {
+        return;
+    }
+
+    let node = ResourceNode::new(NodeKind::Well, building.entrance);
+    shared_knowledge.add_fact(KnowledgeFact::ResourceSite {
+        location: node.location,
+        item: node.kind.item(),
+    });
+    commands.entity(trigger.entity()).insert(node);
+}
+
+// After Startup, so the buildings are on the map: natural nodes are placed
+// where their terrain is
+pub fn spawn_resource_nodes(
+    mut commands: Commands,
+    map: Res<WorldMap>,
+    config: Res<HarvestConfig>,
+    mut shared_knowledge: ResMut<SharedKnowledge>,
+) {
+    let mut nodes = vec![];
+
+    for (kind, count) in [
+        (NodeKind::HuntingGround, config.hunting_grounds),
+        (NodeKind::FishingSpot, config.fishing_spots),
+        (NodeKind::Field, config.fields),
+    ] {
+        for _ in 0..count {
+            let Some(tile) = map.random_tile(|tile| is_site_for(kind, &map, tile)) else {
+                warn!("No site left for a {:?}", kind);
+                break;
+            };
+            let location = map.tile_center(tile);
+
+            let entity = commands
+                .spawn((
+                    Sprite {
+                        color: kind.color(),
+                        custom_size: Some(Vec2::splat(map.tile_size * 0.8)),
+                        ..default()
+                    },
+                    Transform::from_translation(location.with_z(-4.)),
+                    Name::new(format!("{:?}", kind)),
+                ))
+                .id();
+            nodes.push((entity, ResourceNode::new(kind, location)));
+        }
+    }
+
+    for (entity, node) in nodes {
+        shared_knowledge.add_fact(KnowledgeFact::ResourceSite {
+            location: node.location,
+            item: node.kind.item(),
+        });
+        commands.entity(entity).insert(node);
+    }
+}
+
+fn is_site_for(kind: NodeKind, map: &WorldMap, tile: UVec2) -> bool {
+    match kind {
+        NodeKind::Well => false,
+        NodeKind::HuntingGround => map.terrain(tile) == Terrain::Forest,
+        NodeKind::FishingSpot => {
+            map.terrain(tile).is_passable() && map.is_next_to(tile, Terrain::Water)
+        }
+        NodeKind::Field => map.terrain(tile) == Terrain::Grass,
+    }
+}
+
+pub fn regenerate_resource_nodes_system(mut query: Query<&mut ResourceNode>, time: Res<Time>) {
+    for mut node in &mut query {
+        if !node.is_full() {
+            node.regenerate(time.delta_secs());
+        }
+    }
+}
+
+// Nodes fade as they are depleted
+pub fn update_resource_node_sprites(
+    mut query: Query<(&ResourceNode, &mut Sprite), Changed<ResourceNode>>,
+) {
+    for (node, mut sprite) in &mut query {
+        sprite.color.set_alpha(0.25 + 0.75 * node.fill_ratio());
+    }
+}
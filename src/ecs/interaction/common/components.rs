@@ -1,9 +1,13 @@
 use std::collections::VecDeque;
 
-use bevy::ecs::component::Component;
+use bevy::{
+    ecs::{component::Component, entity::Entity},
+    utils::HashMap,
+};
 
 use crate::ecs::{
-    components::InteractionId, talk::interaction::components::KnowledgeSharingInteraction,
+    components::InteractionId, employment::components::JobApplication,
+    talk::interaction::components::KnowledgeSharingInteraction,
     trade::components::TradeNegotiation,
 };
 
@@ -37,8 +41,25 @@ impl AgentInteractionQueue {
 
     // ====================
     // Methods to handle interactions as Target
+    // Keeps the queue sorted by priority, by arrival time within the same priority
     pub fn add(&mut self, item: AgentInteractionItem) {
-        self.received_as_target_queue.push_back(item);
+        let priority = item.kind.priority();
+        let position = self
+            .received_as_target_queue
+            .iter()
+            .position(|queued| queued.kind.priority() < priority)
+            .unwrap_or(self.received_as_target_queue.len());
+
+        self.received_as_target_queue.insert(position, item);
+    }
+
+    // Drops the lowest priority (and latest) interaction once over the limit
+    pub fn overflow(&mut self, max_len: usize) -> Option<AgentInteractionItem> {
+        if self.received_as_target_queue.len() > max_len {
+            self.received_as_target_queue.pop_back()
+        } else {
+            None
+        }
     }
 
     pub fn rm_id(&mut self, rm_id: InteractionId) {
@@ -58,6 +79,10 @@ impl AgentInteractionQueue {
         self.received_as_target_queue.len()
     }
 
+    pub fn drain(&mut self) -> Vec<AgentInteractionItem> {
+        self.received_as_target_queue.drain(..).collect()
+    }
+
     pub fn pop_first(&mut self) -> Option<AgentInteractionItem> {
         match self.received_as_target_queue.pop_front() {
             None => None,
@@ -66,14 +91,79 @@ impl AgentInteractionQueue {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InteractionEndReason {
+    TimedOut,
+    TooFar,
+    PartnerGone,
+    Rejected(RejectionReason),
+    Completed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RejectionReason {
+    // already committed to another interaction
+    Busy,
+    Hostile,
+    NotSelling,
+    QueueTooLong,
+    GroupFull,
+    // evaluated during the protocol (offer too expensive, nothing to share...)
+    Declined,
+}
+
+// Agents this one refuses to interact with, with the resting seconds
+#[derive(Component, Default)]
+pub struct Hostility {
+    towards: HashMap<Entity, f32>,
+}
+
+impl Hostility {
+    pub fn add(&mut self, entity: Entity, secs: f32) {
+        self.towards.insert(entity, secs);
+    }
+
+    pub fn is_hostile_to(&self, entity: Entity) -> bool {
+        self.towards.contains_key(&entity)
+    }
+
+    pub fn progress(&mut self, time: f32) {
+        self.towards.retain(|_, secs| {
+            *secs -= time;
+            *secs > 0.
+        });
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct AgentInteractionItem {
     pub id: InteractionId,
     pub kind: AgentInteractionKind,
 }
 
+impl AgentInteractionItem {
+    pub fn source(&self) -> Entity {
+        match &self.kind {
+            AgentInteractionKind::Trade(trade) => trade.partner,
+            AgentInteractionKind::Ask(sharing) => sharing.source,
+            AgentInteractionKind::Hire(application) => application.worker,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum AgentInteractionKind {
     Trade(TradeNegotiation),
     Ask(KnowledgeSharingInteraction),
+    Hire(JobApplication),
+}
+
+impl AgentInteractionKind {
+    // paying customers before questions and job applicants
+    pub fn priority(&self) -> u8 {
+        match self {
+            AgentInteractionKind::Trade(_) => 1,
+            AgentInteractionKind::Ask(_) | AgentInteractionKind::Hire(_) => 0,
+        }
+    }
 }
@@ -1,42 +1,44 @@
 use bevy::ecs::{entity::Entity, event::Event};
 
 use crate::ecs::components::InteractionId;
-use crate::ecs::interaction::common::components::AgentInteractionItem;
+use crate::ecs::interaction::common::components::{AgentInteractionItem, InteractionEndReason};
 
+// Sent by the source, the target decides to queue or reject it
 #[derive(Event, Debug)]
-pub struct InteractionStarted {
+pub struct InteractionRequested {
+    pub source: Entity,
     pub target: Entity,
     pub item: AgentInteractionItem,
 }
 
 #[derive(Event, Debug)]
-pub struct SourceStartInteraction {
+pub struct InteractionStarted {
     pub target: Entity,
+    pub accepted_by: Entity,
+    pub item: AgentInteractionItem,
 }
 
-
 #[derive(Event, Debug)]
-pub struct InteractionTimedOut {
-    // TODO: change to InteractionExit with reason enum (timeout,
-    // force quit etc)
-    pub id: InteractionId,
+pub struct SourceStartInteraction {
+    pub target: Entity,
 }
 
+// The only way an interaction (waiting, queued or running) finishes.
+// Every protocol (trade, talk) and every action waiting for an interaction
+// reacts to this single event
 #[derive(Event, Debug)]
-pub struct WaitingInteractionTimedOut {
-    // TODO: change to WaitingInteractionExit with reason enum (timeout,
-    // force quit etc)
+pub struct InteractionEnded {
     pub id: InteractionId,
     pub source: Entity,
     pub target: Entity,
+    pub reason: InteractionEndReason,
 }
 
-// The purpose of this event is only to deal with borrow checker:
-// In "handle_interaction_starting_for_target_system"
-// we need to check two Interacting instances - one from the source
-// and other from the target - while beeing able to mutate the one from
-// the target. Since this is not allowed, we need this event to mutate elsewhere
+// Triggered once both source and target are ready, so every protocol
+// (trade, talk) starts right away through its observers
 #[derive(Event, Debug)]
-pub struct TargetIsReadyToStartInteracting {
+pub struct InteractionReady {
+    pub id: InteractionId,
+    pub source: Entity,
     pub target: Entity,
 }
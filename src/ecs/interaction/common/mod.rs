@@ -1,3 +1,4 @@
 pub mod components;
 pub mod events;
+pub mod resources;
 pub mod systems;
@@ -0,0 +1,60 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::ecs::components::InteractionId;
+
+#[derive(Resource, Debug, Clone)]
+pub struct InteractionConfig {
+    // over this many waiting requests, the lowest priority one is rejected
+    pub max_queue_len: usize,
+    // how long a target refuses a source that left it waiting
+    pub hostility_secs: f32,
+    // partners (or group participants and the group center) farther apart
+    // than this end the interaction
+    pub max_interaction_distance: f32,
+    // agents join a group when this close to its center
+    pub group_join_radius: f32,
+    pub max_group_participants: usize,
+    // how long a group waits for participants before becoming active
+    pub group_gathering_secs: f32,
+    pub group_active_secs: f32,
+    // an agent finding nobody to ask looks again after this long, and gives
+    // up after max_ask_retries
+    pub ask_retry_secs: f32,
+    pub max_ask_retries: u32,
+}
+
+impl Default for InteractionConfig {
+    fn default() -> Self {
+        Self {
+            max_queue_len: 10,
+            hostility_secs: 30.,
+            max_interaction_distance: 500.,
+            group_join_radius: 100.,
+            max_group_participants: 5,
+            group_gathering_secs: 2.,
+            group_active_secs: 10.,
+            ask_retry_secs: 3.,
+            max_ask_retries: 3,
+        }
+    }
+}
+
+// Interaction entities by id
+#[derive(Resource, Default)]
+pub struct InteractionIndex {
+    by_id: HashMap<InteractionId, Entity>,
+}
+
+impl InteractionIndex {
+    pub fn get(&self, id: InteractionId) -> Option<Entity> {
+        self.by_id.get(&id).copied()
+    }
+
+    pub fn insert(&mut self, id: InteractionId, entity: Entity) {
+        self.by_id.insert(id, entity);
+    }
+
+    pub fn remove(&mut self, id: InteractionId) {
+        self.by_id.remove(&id);
+    }
+}
@@ -3,172 +3,174 @@ use bevy::prelude::*;
 use crate::ecs::{
     components::*,
     interaction::common::{
-        components::AgentInteractionQueue,
-        events::{
-            InteractionTimedOut, TargetIsReadyToStartInteracting, WaitingInteractionTimedOut,
-        },
+        components::{AgentInteractionQueue, Hostility, InteractionEndReason},
+        events::{InteractionEnded, InteractionReady},
+        resources::{InteractionConfig, InteractionIndex},
     },
     logs::AddLogEntry,
+    movement::resources::MovementConfig,
 };
 
-// This system must be generic for starting every single interaction (source)
-pub fn handle_interaction_starting_for_source_system(
-    mut query: Query<(Entity, &mut Interacting)>,
-    mut add_log_writer: EventWriter<AddLogEntry>,
+pub fn index_added_interaction(
+    trigger: Trigger<OnAdd, AgentInteraction>,
+    query: Query<&AgentInteraction>,
+    mut index: ResMut<InteractionIndex>,
 ) {
-    for (entity, mut entity_interacting) in &mut query {
-        if entity != entity_interacting.source {
-            continue;
-        }
+    if let Ok(interaction) = query.get(trigger.entity()) {
+        index.insert(interaction.id, trigger.entity());
+    }
+}
+
+// The interaction entity is gone: its participants are released as well
+pub fn release_removed_interaction(
+    trigger: Trigger<OnRemove, AgentInteraction>,
+    query: Query<&AgentInteraction>,
+    participant_query: Query<&Interacting>,
+    mut index: ResMut<InteractionIndex>,
+    mut commands: Commands,
+) {
+    let Ok(interaction) = query.get(trigger.entity()) else {
+        return;
+    };
 
-        if entity_interacting.is_waiting() {
-            add_log_writer.send(AddLogEntry::new(
-                entity,
-                format!(
-                    "Interaction {} -> Set Ready as Source",
-                    entity_interacting.id
-                )
-                .as_str(),
-            ));
-            // start the interaction for the source
-            entity_interacting.set_ready();
+    index.remove(interaction.id);
+
+    for participant in [interaction.source, interaction.target] {
+        if participant_query
+            .get(participant)
+            .is_ok_and(|v| v.interaction == trigger.entity())
+        {
+            commands.entity(participant).remove::<Interacting>();
         }
     }
 }
 
-// This system must be generic for starting every single interaction (target)
-pub fn handle_interaction_starting_for_target_system(
-    query: Query<(Entity, &Transform, &Interacting)>,
-    source_query: Query<(&Transform, &Interacting)>,
+// This system must be generic for starting every single interaction.
+// The target joins first (when it accepts the request), the source joins
+// once it is free; then they only have to get close to each other
+pub fn handle_interaction_starting_system(
+    mut query: Query<(Entity, &mut AgentInteraction)>,
+    agent_query: Query<(&Transform, Option<&Interacting>)>,
+    config: Res<MovementConfig>,
     mut add_log_writer: EventWriter<AddLogEntry>,
     mut command: Commands,
 ) {
-    for (entity, entity_transform, entity_interacting) in &query {
-        if entity != entity_interacting.target {
+    for (entity, mut interaction) in &mut query {
+        if !interaction.is_accepted() {
+            continue;
+        }
+
+        let Ok((source_transform, source_interacting)) = agent_query.get(interaction.source) else {
+            continue;
+        };
+
+        // the source is still busy with another interaction
+        if source_interacting.is_none_or(|v| v.interaction != entity) {
             continue;
         }
 
-        // With the rules here, it means the source get ready first
-        // and the target get ready later.
-
-        if entity_interacting.is_waiting() {
-            // check if source is ready:
-            // 1. close enough
-            // 2. dealing with this particular interaction right now (by interaction id)
-            // 3. is already ready to start interacting
-            if let Ok((source_transform, source_interacting)) =
-                source_query.get(entity_interacting.source)
-            {
-                if source_interacting.id != entity_interacting.id {
-                    continue;
-                }
-
-                // TODO: confirm this is safe and work as expected
-                if source_interacting.is_waiting() {
-                    continue;
-                }
-
-                if source_transform
-                    .translation
-                    .distance(entity_transform.translation)
-                    <= 50.
-                {
-                    add_log_writer.send(AddLogEntry::new(
-                        entity,
-                        format!(
-                            "Interaction {} -> Set Ready as Target",
-                            entity_interacting.id
-                        )
-                        .as_str(),
-                    ));
-
-                    command.trigger(TargetIsReadyToStartInteracting { target: entity });
-                }
+        let Ok((target_transform, _)) = agent_query.get(interaction.target) else {
+            continue;
+        };
+
+        if source_transform
+            .translation
+            .distance(target_transform.translation)
+            <= config.interaction_radius
+        {
+            for participant in [interaction.source, interaction.target] {
+                add_log_writer.send(AddLogEntry::new(
+                    participant,
+                    format!("Interaction {} -> Ready", interaction.id).as_str(),
+                ));
             }
+
+            interaction.set_ready();
+
+            command.trigger(InteractionReady {
+                id: interaction.id,
+                source: interaction.source,
+                target: interaction.target,
+            });
         }
     }
 }
 
-pub fn target_is_ready_to_start_interacting(
-    trigger: Trigger<TargetIsReadyToStartInteracting>,
-    mut query: Query<&mut Interacting>,
+// Both sides are ready: the interaction is active while the protocols
+// (trade, talk) run
+pub fn activate_ready_interaction(
+    trigger: Trigger<InteractionReady>,
+    index: Res<InteractionIndex>,
+    mut query: Query<&mut AgentInteraction>,
 ) {
-    if let Ok(mut target_interacting) = query.get_mut(trigger.target) {
-        // start the interaction for the target
-        target_interacting.set_ready();
+    if let Some(mut interaction) = index.get(trigger.id).and_then(|v| query.get_mut(v).ok()) {
+        interaction.set_active();
     }
 }
 
 pub fn interaction_agents_move_on_system(
-    query: Query<(Entity, &Transform, &Interacting)>,
-    partner_query: Query<(&Transform, &Interacting)>,
+    query: Query<&AgentInteraction>,
+    agent_query: Query<&Transform>,
+    config: Res<InteractionConfig>,
     mut add_log_writer: EventWriter<AddLogEntry>,
     mut command: Commands,
 ) {
-    for (entity, entity_transform, entity_interacting) in &query {
-        if entity_interacting.is_waiting() {
+    for interaction in &query {
+        if interaction.is_accepted() || interaction.is_finished() {
             continue;
         }
 
-        let partner = if entity_interacting.source == entity {
-            entity_interacting.target
-        } else {
-            entity_interacting.source
+        let (Ok(source_transform), Ok(target_transform)) = (
+            agent_query.get(interaction.source),
+            agent_query.get(interaction.target),
+        ) else {
+            continue;
         };
 
-        if let Ok((partner_transform, partner_interacting)) = &partner_query.get(partner) {
-            if partner_interacting.is_waiting() {
-                continue;
-            }
-
-            if partner_interacting.id != entity_interacting.id {
-                continue;
-            }
-
-            if partner_transform
-                .translation
-                .distance(entity_transform.translation)
-                > 500.
-            {
+        if source_transform
+            .translation
+            .distance(target_transform.translation)
+            > config.max_interaction_distance
+        {
+            for participant in [interaction.source, interaction.target] {
                 add_log_writer.send(AddLogEntry::new(
-                    entity,
+                    participant,
                     format!(
                         "Partners are too far away to interact. Finishing this interaction {}",
-                        partner_interacting.id,
+                        interaction.id,
                     )
                     .as_str(),
                 ));
-
-                add_log_writer.send(AddLogEntry::new(
-                    partner,
-                    format!(
-                        "Partners are too far away to interact. Finishing this interaction {}",
-                        partner_interacting.id,
-                    )
-                    .as_str(),
-                ));
-
-                command.trigger(InteractionTimedOut {
-                    id: partner_interacting.id,
-                });
             }
+
+            command.trigger(InteractionEnded {
+                id: interaction.id,
+                source: interaction.source,
+                target: interaction.target,
+                reason: InteractionEndReason::TooFar,
+            });
         }
     }
 }
 
 pub fn interaction_timeout_system(
-    mut query: Query<&mut Interacting>,
+    mut query: Query<&mut AgentInteraction>,
     mut command: Commands,
     time: Res<Time>,
 ) {
-    for mut interacting in &mut query {
-        if interacting.get_resting_duration() > 0. {
-            interacting.progress(time.delta_secs());
-        } else if interacting.is_timed_out() {
+    for mut interaction in &mut query {
+        if !interaction.lifecycle().has_elapsed() {
+            interaction.lifecycle_mut().progress(time.delta_secs());
+        } else if interaction.is_finished() {
             // nothing
         } else {
-            interacting.set_timed_out();
-            command.trigger(InteractionTimedOut { id: interacting.id });
+            interaction.set_finished();
+            command.trigger(InteractionEnded {
+                id: interaction.id,
+                source: interaction.source,
+                target: interaction.target,
+                reason: InteractionEndReason::TimedOut,
+            });
         }
     }
 }
@@ -179,47 +181,95 @@ pub fn waiting_interaction_timeout_system(
     time: Res<Time>,
 ) {
     for mut waiting in &mut query {
-        if waiting.get_resting_duration() > 0. {
-            waiting.progress(time.delta_secs());
-        } else if waiting.is_timed_out() {
+        if !waiting.lifecycle().has_elapsed() {
+            waiting.lifecycle_mut().progress(time.delta_secs());
+        } else if waiting.is_finished() {
             // nothing
         } else {
-            waiting.set_timed_out();
-            command.trigger(WaitingInteractionTimedOut {
+            waiting.set_finished();
+            command.trigger(InteractionEnded {
                 id: waiting.id,
                 source: waiting.source,
                 target: waiting.target,
+                reason: InteractionEndReason::TimedOut,
             });
         }
     }
 }
 
-pub fn remove_timed_out_interaction_from_agent_queue(
-    trigger: Trigger<InteractionTimedOut>,
-    mut query: Query<(&mut AgentInteractionQueue, &Interacting)>,
+// Generic part of finishing an interaction, whatever the reason. Protocols
+// (trade, talk) and the actions waiting for the interaction clean up their
+// own components in their InteractionEnded observers; participants still
+// pointing to the despawned interaction entity are released anyway
+pub fn finish_ended_interaction(
+    trigger: Trigger<InteractionEnded>,
+    index: Res<InteractionIndex>,
+    mut interaction_query: Query<&mut AgentInteraction>,
+    mut waiting_query: Query<&mut WaitingInteraction>,
+    mut queue_query: Query<&mut AgentInteractionQueue>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+    mut commands: Commands,
 ) {
-    for (mut agent_interation_queue, _) in &mut query {
-        if !agent_interation_queue.is_empty() {
-            agent_interation_queue.rm_id(trigger.id);
+    // despawned once every observer is done with it
+    if let Some(entity) = index.get(trigger.id) {
+        if let Ok(mut interaction) = interaction_query.get_mut(entity) {
+            interaction.set_finished();
+            commands.entity(entity).despawn();
+        }
+    }
+
+    for entity in [trigger.source, trigger.target] {
+        if let Ok(mut waiting) = waiting_query.get_mut(entity) {
+            if waiting.id == trigger.id {
+                waiting.set_finished();
+            }
+        }
+
+        add_log_writer.send(AddLogEntry::new(
+            entity,
+            format!("Interaction {} ended: {:?}", trigger.id, trigger.reason).as_str(),
+        ));
+    }
+
+    // not started by the target yet
+    if let Ok(mut target_queue) = queue_query.get_mut(trigger.target) {
+        target_queue.rm_id(trigger.id);
+    }
+
+    // accepted by the target while the source was busy with another interaction
+    if let Ok(mut source_queue) = queue_query.get_mut(trigger.source) {
+        if source_queue
+            .get_ready_interaction()
+            .is_some_and(|item| item.id == trigger.id)
+        {
+            source_queue.clean_ready_interaction();
         }
     }
 }
 
-pub fn remove_timed_out_waiting_interaction_from_agent_queue(
-    trigger: Trigger<WaitingInteractionTimedOut>,
-    mut query: Query<&mut AgentInteractionQueue>,
+// A target that accepted an interaction and was left waiting (the source
+// never showed up or walked away) refuses that source for a while
+pub fn resent_partner_that_left(
+    trigger: Trigger<InteractionEnded>,
+    mut query: Query<(&Interacting, &mut Hostility)>,
+    config: Res<InteractionConfig>,
 ) {
-    // if let Ok(mut agent_interation_queue) = query.get_mut(trigger.source) {
-    //     if !agent_interation_queue.is_empty() {
-    //         agent_interation_queue.rm_id(trigger.id);
-    //     }
-    // }
-    if let Ok(mut agent_interation_queue) = query.get_mut(trigger.target) {
-        if !agent_interation_queue.is_empty() {
-            agent_interation_queue.rm_id(trigger.id);
+    if !matches!(
+        trigger.reason,
+        InteractionEndReason::TimedOut | InteractionEndReason::TooFar
+    ) {
+        return;
+    }
+
+    if let Ok((interacting, mut hostility)) = query.get_mut(trigger.target) {
+        if interacting.id == trigger.id {
+            hostility.add(trigger.source, config.hostility_secs);
         }
-        // maybe we have to check if target started the interaction.
-        // If yes, the interaction won't exist in the queue and the target will be
-        // interacting until timeout.
+    }
+}
+
+pub fn hostility_cooldown_system(mut query: Query<&mut Hostility>, time: Res<Time>) {
+    for mut hostility in &mut query {
+        hostility.progress(time.delta_secs());
     }
 }
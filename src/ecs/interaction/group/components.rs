@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use rand::random;
+
+use crate::{
+    core::item::ItemEnum,
+    ecs::{
+        action::components::ActionLifecycle,
+        components::{InteractionId, InteractionPhase},
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GroupInteractionKind {
+    // agents looking for sellers of the same item pool what they know
+    GossipCircle { topic: ItemEnum },
+    // buyers lined up at a seller about to run out bid for its last units
+    Auction { item: ItemEnum },
+}
+
+// Lives on its own entity, shared by every participant. The group gathers
+// participants (Accepted) for a while, then becomes Active once there are
+// enough of them and Finished when it ends.
+#[derive(Component, Debug)]
+pub struct GroupInteraction {
+    pub id: InteractionId,
+    pub kind: GroupInteractionKind,
+    pub host: Entity,
+    pub center: Vec3,
+    participants: Vec<Entity>,
+    min_participants: usize,
+    max_participants: usize,
+    lifecycle: ActionLifecycle,
+    phase: InteractionPhase,
+}
+
+impl GroupInteraction {
+    pub fn new(
+        kind: GroupInteractionKind,
+        host: Entity,
+        center: Vec3,
+        max_participants: usize,
+        gathering_duration: f32,
+    ) -> Self {
+        Self {
+            id: random(),
+            kind,
+            host,
+            center,
+            participants: vec![],
+            min_participants: 2,
+            max_participants,
+            lifecycle: ActionLifecycle::with_duration(gathering_duration),
+            phase: InteractionPhase::Accepted,
+        }
+    }
+
+    pub fn with_min_participants(mut self, min_participants: usize) -> Self {
+        self.min_participants = min_participants;
+        self
+    }
+
+    pub fn lifecycle(&self) -> &ActionLifecycle {
+        &self.lifecycle
+    }
+
+    pub fn lifecycle_mut(&mut self) -> &mut ActionLifecycle {
+        &mut self.lifecycle
+    }
+
+    pub fn participants(&self) -> &[Entity] {
+        &self.participants
+    }
+
+    pub fn contains(&self, agent: Entity) -> bool {
+        self.participants.contains(&agent)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.participants.len() >= self.max_participants
+    }
+
+    pub fn has_enough_participants(&self) -> bool {
+        self.participants.len() >= self.min_participants
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.participants.is_empty()
+    }
+
+    pub fn is_gathering(&self) -> bool {
+        self.phase == InteractionPhase::Accepted
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.phase == InteractionPhase::Active
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.phase == InteractionPhase::Finished
+    }
+
+    pub fn join(&mut self, agent: Entity) {
+        if !self.contains(agent) {
+            self.participants.push(agent);
+        }
+    }
+
+    pub fn leave(&mut self, agent: Entity) {
+        self.participants.retain(|v| *v != agent);
+    }
+
+    pub fn set_active(&mut self, duration: f32) {
+        self.phase = InteractionPhase::Active;
+        self.lifecycle = ActionLifecycle::with_duration(duration);
+    }
+
+    pub fn set_finished(&mut self) {
+        self.phase = InteractionPhase::Finished;
+    }
+}
+
+// Set on every participant of a group interaction
+#[derive(Component, Debug)]
+pub struct GroupMember {
+    pub group: Entity,
+    pub id: InteractionId,
+}
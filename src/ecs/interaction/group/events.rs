@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+
+use crate::ecs::{
+    components::InteractionId, interaction::common::components::InteractionEndReason,
+};
+
+#[derive(Event, Debug)]
+pub struct JoinGroupInteraction {
+    pub group: Entity,
+    pub agent: Entity,
+}
+
+#[derive(Event, Debug)]
+pub struct LeaveGroupInteraction {
+    pub group: Entity,
+    pub agent: Entity,
+    pub reason: InteractionEndReason,
+}
+
+#[derive(Event, Debug)]
+pub struct EndGroupInteraction {
+    pub group: Entity,
+    pub reason: InteractionEndReason,
+}
+
+// Sent to every participant once it is out of the group, whatever the reason
+// (it left, the group ended, or it could not join at all)
+#[derive(Event, Debug)]
+pub struct LeftGroupInteraction {
+    pub group: Entity,
+    pub id: InteractionId,
+    pub host: Entity,
+    pub agent: Entity,
+    pub reason: InteractionEndReason,
+}
+
+// Enough participants gathered: every protocol runs through its observers
+#[derive(Event, Debug)]
+pub struct GroupInteractionActive {
+    pub group: Entity,
+}
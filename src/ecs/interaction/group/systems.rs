@@ -0,0 +1,205 @@
+use bevy::prelude::*;
+
+use crate::ecs::{
+    components::Interacting,
+    interaction::{
+        common::{
+            components::{InteractionEndReason, RejectionReason},
+            resources::InteractionConfig,
+        },
+        group::{
+            components::{GroupInteraction, GroupMember},
+            events::{
+                EndGroupInteraction, GroupInteractionActive, JoinGroupInteraction,
+                LeaveGroupInteraction, LeftGroupInteraction,
+            },
+        },
+    },
+    logs::AddLogEntry,
+};
+
+pub fn join_group_interaction(
+    trigger: Trigger<JoinGroupInteraction>,
+    mut group_query: Query<&mut GroupInteraction>,
+    agent_query: Query<(Has<GroupMember>, Has<Interacting>)>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+    mut commands: Commands,
+) {
+    let Ok(mut group) = group_query.get_mut(trigger.group) else {
+        return;
+    };
+
+    let rejection = if group.is_finished() {
+        Some(InteractionEndReason::PartnerGone)
+    } else if agent_query
+        .get(trigger.agent)
+        .is_ok_and(|(is_member, is_interacting)| is_member || is_interacting)
+    {
+        Some(InteractionEndReason::Rejected(RejectionReason::Busy))
+    } else if group.is_full() {
+        Some(InteractionEndReason::Rejected(RejectionReason::GroupFull))
+    } else {
+        None
+    };
+
+    if let Some(reason) = rejection {
+        commands.trigger(LeftGroupInteraction {
+            group: trigger.group,
+            id: group.id,
+            host: group.host,
+            agent: trigger.agent,
+            reason,
+        });
+        return;
+    }
+
+    group.join(trigger.agent);
+
+    commands.entity(trigger.agent).insert(GroupMember {
+        group: trigger.group,
+        id: group.id,
+    });
+
+    add_log_writer.send(AddLogEntry::new(
+        trigger.agent,
+        format!("Joined group Interaction {}", group.id).as_str(),
+    ));
+}
+
+pub fn leave_group_interaction(
+    trigger: Trigger<LeaveGroupInteraction>,
+    mut group_query: Query<&mut GroupInteraction>,
+    mut commands: Commands,
+) {
+    let Ok(mut group) = group_query.get_mut(trigger.group) else {
+        return;
+    };
+
+    if !group.contains(trigger.agent) {
+        return;
+    }
+
+    group.leave(trigger.agent);
+
+    commands.entity(trigger.agent).remove::<GroupMember>();
+    commands.trigger(LeftGroupInteraction {
+        group: trigger.group,
+        id: group.id,
+        host: group.host,
+        agent: trigger.agent,
+        reason: trigger.reason,
+    });
+
+    if group.is_empty() {
+        commands.trigger(EndGroupInteraction {
+            group: trigger.group,
+            reason: trigger.reason,
+        });
+    } else if group.is_active() && !group.has_enough_participants() {
+        // a finished group is being closed by its protocol, every
+        // participant leaves with its own outcome
+        commands.trigger(EndGroupInteraction {
+            group: trigger.group,
+            reason: InteractionEndReason::PartnerGone,
+        });
+    }
+}
+
+pub fn end_group_interaction(
+    trigger: Trigger<EndGroupInteraction>,
+    mut group_query: Query<&mut GroupInteraction>,
+    mut commands: Commands,
+) {
+    let Ok(mut group) = group_query.get_mut(trigger.group) else {
+        return;
+    };
+
+    group.set_finished();
+
+    for agent in group.participants() {
+        commands.entity(*agent).remove::<GroupMember>();
+        commands.trigger(LeftGroupInteraction {
+            group: trigger.group,
+            id: group.id,
+            host: group.host,
+            agent: *agent,
+            reason: trigger.reason,
+        });
+    }
+
+    commands.entity(trigger.group).despawn();
+}
+
+pub fn log_left_group_interaction(
+    trigger: Trigger<LeftGroupInteraction>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+) {
+    add_log_writer.send(AddLogEntry::new(
+        trigger.agent,
+        format!(
+            "Left group Interaction {}: {:?}",
+            trigger.id, trigger.reason
+        )
+        .as_str(),
+    ));
+}
+
+pub fn group_interaction_timeout_system(
+    mut query: Query<(Entity, &mut GroupInteraction)>,
+    config: Res<InteractionConfig>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (entity, mut group) in &mut query {
+        if group.is_finished() {
+            continue;
+        }
+
+        if !group.lifecycle().has_elapsed() {
+            group.lifecycle_mut().progress(time.delta_secs());
+        } else if group.is_gathering() && group.has_enough_participants() {
+            group.set_active(config.group_active_secs);
+            commands.trigger(GroupInteractionActive { group: entity });
+        } else {
+            group.set_finished();
+            commands.trigger(EndGroupInteraction {
+                group: entity,
+                reason: InteractionEndReason::TimedOut,
+            });
+        }
+    }
+}
+
+// Same rule as interaction_agents_move_on_system, for every participant
+// against the group center
+pub fn group_interaction_move_on_system(
+    query: Query<(Entity, &GroupInteraction)>,
+    agent_query: Query<&Transform>,
+    config: Res<InteractionConfig>,
+    mut commands: Commands,
+) {
+    for (entity, group) in &query {
+        if group.is_finished() {
+            continue;
+        }
+
+        for agent in group.participants() {
+            let reason = match agent_query.get(*agent) {
+                Err(_) => InteractionEndReason::PartnerGone,
+                Ok(transform)
+                    if transform.translation.distance(group.center)
+                        > config.max_interaction_distance =>
+                {
+                    InteractionEndReason::TooFar
+                }
+                Ok(_) => continue,
+            };
+
+            commands.trigger(LeaveGroupInteraction {
+                group: entity,
+                agent: *agent,
+                reason,
+            });
+        }
+    }
+}
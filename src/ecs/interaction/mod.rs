@@ -1,4 +1,5 @@
-pub mod plugin;
 pub mod common;
+pub mod group;
+pub mod plugin;
 mod source;
 mod target;
@@ -2,7 +2,9 @@ use bevy::prelude::*;
 use bevy::state::condition::in_state;
 
 use crate::ecs::game_state::GameState;
+use crate::ecs::interaction::common::resources::{InteractionConfig, InteractionIndex};
 use crate::ecs::interaction::common::systems::*;
+use crate::ecs::interaction::group::systems::*;
 use crate::ecs::interaction::source::systems::*;
 use crate::ecs::interaction::target::systems::*;
 
@@ -10,19 +12,29 @@ pub struct BaseInteractionPlugin;
 
 impl Plugin for BaseInteractionPlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(receive_interaction_started_system)
+        app.init_resource::<InteractionConfig>()
+            .init_resource::<InteractionIndex>()
+            .add_observer(index_added_interaction)
+            .add_observer(release_removed_interaction)
+            .add_observer(evaluate_interaction_request)
+            .add_observer(receive_interaction_started_system)
             .add_observer(start_interaction_as_source_system)
             .add_observer(wait_finish_interaction_to_start_new_interaction_as_source_system)
-            .add_observer(remove_timed_out_interaction_from_agent_queue)
-            .add_observer(remove_timed_out_waiting_interaction_from_agent_queue)
-            .add_observer(target_is_ready_to_start_interacting)
+            .add_observer(finish_ended_interaction)
+            .add_observer(activate_ready_interaction)
+            .add_observer(resent_partner_that_left)
+            .add_observer(join_group_interaction)
+            .add_observer(leave_group_interaction)
+            .add_observer(end_group_interaction)
+            .add_observer(log_left_group_interaction)
             .add_systems(
                 First,
                 (
+                    hostility_cooldown_system,
                     interaction_timeout_system,
+                    group_interaction_timeout_system,
                     waiting_interaction_timeout_system,
-                    handle_interaction_starting_for_source_system,
-                    handle_interaction_starting_for_target_system,
+                    handle_interaction_starting_system,
                 )
                     .chain()
                     .run_if(in_state(GameState::Running)),
@@ -35,6 +47,7 @@ impl Plugin for BaseInteractionPlugin {
                 Update,
                 (
                     interaction_agents_move_on_system,
+                    group_interaction_move_on_system,
                 )
                     .run_if(in_state(GameState::Running)),
             );
@@ -4,8 +4,9 @@ use crate::{
     ecs::{
         components::{Interacting, WaitingInteraction},
         interaction::common::{
-            components::{AgentInteractionKind, AgentInteractionQueue},
-            events::{InteractionStarted, InteractionTimedOut},
+            components::{AgentInteractionKind, AgentInteractionQueue, InteractionEndReason},
+            events::{InteractionEnded, InteractionStarted},
+            resources::InteractionIndex,
         },
         logs::AddLogEntry,
     },
@@ -15,52 +16,63 @@ use crate::{
 pub fn receive_interaction_started_system(
     trigger: Trigger<InteractionStarted>,
     mut query: Query<(
-        &WaitingInteraction,
+        &mut WaitingInteraction,
         &mut AgentInteractionQueue,
         Option<&Interacting>,
     )>,
     mut add_log_writer: EventWriter<AddLogEntry>,
     mut commands: Commands,
 ) {
-    if let Ok((waiting, mut agent_queue, maybe_interacting)) = query.get_mut(trigger.target) {
+    let partner_gone = InteractionEnded {
+        id: trigger.item.id,
+        source: trigger.target,
+        target: trigger.accepted_by,
+        reason: InteractionEndReason::PartnerGone,
+    };
+
+    let Ok((mut waiting, mut agent_queue, maybe_interacting)) = query.get_mut(trigger.target)
+    else {
+        // the source gave up waiting (or is gone)
+        commands.trigger(partner_gone);
+        return;
+    };
+
+    add_log_writer.send(AddLogEntry::new(
+        trigger.target,
+        format!(
+            "Received InteractionStarted event Id: {}. WaitingInteraction ID {}",
+            trigger.item.id, waiting.id
+        )
+        .as_str(),
+    ));
+
+    if waiting.id != trigger.item.id || waiting.is_finished() {
+        commands.trigger(partner_gone);
+
+        return;
+    }
+
+    waiting.set_accepted();
+    agent_queue.interaction_ready(trigger.item.clone());
+
+    if let Some(interacting) = maybe_interacting {
+        add_log_writer.send(AddLogEntry::new(
+            trigger.target,
+            format!("Currently interacting {}", interacting.id).as_str(),
+        ));
+    } else {
         add_log_writer.send(AddLogEntry::new(
             trigger.target,
             format!(
-                "Received InteractionStarted event Id: {}. WaitingInteraction ID {}",
-                trigger.item.id, waiting.id
+                "Triggered SourceStartInteraction event for interaction: {}",
+                trigger.item.id
             )
             .as_str(),
         ));
 
-        if waiting.id != trigger.item.id {
-            commands.trigger(InteractionTimedOut {
-                id: trigger.item.id,
-            });
-
-            return;
-        }
-
-        agent_queue.interaction_ready(trigger.item.clone());
-
-        if maybe_interacting.is_none() {
-            add_log_writer.send(AddLogEntry::new(
-                trigger.target,
-                format!(
-                    "Triggered SourceStartInteraction event for interaction: {}",
-                    trigger.item.id
-                )
-                .as_str(),
-            ));
-
-            commands.trigger(SourceStartInteraction {
-                target: trigger.target,
-            });
-        } else {
-            add_log_writer.send(AddLogEntry::new(
-                trigger.target,
-                format!("Currently interacting {}", maybe_interacting.unwrap().id).as_str(),
-            ));
-        }
+        commands.trigger(SourceStartInteraction {
+            target: trigger.target,
+        });
     }
 }
 
@@ -94,6 +106,7 @@ pub fn wait_finish_interaction_to_start_new_interaction_as_source_system(
 pub fn start_interaction_as_source_system(
     trigger: Trigger<SourceStartInteraction>,
     mut query: Query<(&WaitingInteraction, &mut AgentInteractionQueue)>,
+    index: Res<InteractionIndex>,
     mut add_log_writer: EventWriter<AddLogEntry>,
     mut commands: Commands,
 ) {
@@ -103,6 +116,12 @@ pub fn start_interaction_as_source_system(
                 panic!("waiting.id != ready_interaction.id should not happen")
             }
 
+            // the interaction ended meanwhile, InteractionEnded did the cleanup
+            let Some(interaction) = index.get(ready_interaction.id) else {
+                return;
+            };
+            let interacting = Interacting::new(interaction, ready_interaction.id);
+
             add_log_writer.send(AddLogEntry::new(
                 trigger.target,
                 format!(
@@ -116,31 +135,24 @@ pub fn start_interaction_as_source_system(
                 AgentInteractionKind::Ask(sharing) => {
                     commands
                         .entity(sharing.source)
-                        .insert((
-                            sharing.clone(),
-                            Interacting::new_with_id(
-                                ready_interaction.id,
-                                sharing.source,
-                                sharing.target,
-                            ),
-                        ))
+                        .insert((sharing.clone(), interacting))
                         .remove::<WaitingInteraction>();
                 }
                 AgentInteractionKind::Trade(trade_negotiation) => {
-                    let interaction = Interacting::new_with_id(
-                        ready_interaction.id,
-                        trade_negotiation.partner,
-                        waiting.target,
-                    );
-
                     commands
                         .entity(trade_negotiation.partner)
                         .insert((
-                            interaction,
+                            interacting,
                             trade_negotiation.clone_for_source(waiting.target),
                         ))
                         .remove::<WaitingInteraction>();
                 }
+                AgentInteractionKind::Hire(application) => {
+                    commands
+                        .entity(application.worker)
+                        .insert((application.clone(), interacting))
+                        .remove::<WaitingInteraction>();
+                }
             }
 
             agent_queue.clean_ready_interaction();
@@ -0,0 +1,247 @@
+use bevy::prelude::*;
+
+use crate::ecs::{
+    agent::Agent,
+    components::{AgentInteraction, Interacting, InteractionPhase, WaitingInteraction},
+    interaction::{
+        common::{
+            components::{
+                AgentInteractionItem, AgentInteractionKind, AgentInteractionQueue, Hostility,
+                InteractionEndReason, RejectionReason,
+            },
+            events::{InteractionEnded, InteractionRequested, InteractionStarted},
+            resources::InteractionConfig,
+        },
+        group::components::GroupMember,
+    },
+    logs::AddLogEntry,
+    sell::actions::components::Selling,
+    trade::components::TradeInteraction,
+};
+
+fn is_not_selling(item: &AgentInteractionItem, agent: &Agent, is_selling: bool) -> bool {
+    match &item.kind {
+        AgentInteractionKind::Trade(trade) => {
+            !is_selling || agent.inventory.get_qty(trade.item) == 0
+        }
+        AgentInteractionKind::Ask(_) | AgentInteractionKind::Hire(_) => false,
+    }
+}
+
+// Interacting already, about to interact elsewhere (its own request was
+// accepted) or taking part in a group interaction
+fn is_busy(waiting: Option<&WaitingInteraction>, interacting: bool, in_group: bool) -> bool {
+    interacting || in_group || waiting.is_some_and(|v| v.phase() == InteractionPhase::Accepted)
+}
+
+// The target decides right away if the request is queued or rejected, so the
+// source never waits for a request that will not be accepted. A busy target
+// still queues requests, until its queue is full: then it is Busy rather than
+// pushing out one already waiting.
+pub fn evaluate_interaction_request(
+    trigger: Trigger<InteractionRequested>,
+    mut target_query: Query<(
+        &Agent,
+        &mut AgentInteractionQueue,
+        Option<&Hostility>,
+        Has<Selling>,
+    )>,
+    busy_query: Query<(Has<Interacting>, Has<GroupMember>)>,
+    mut waiting_query: Query<&mut WaitingInteraction>,
+    config: Res<InteractionConfig>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+    mut commands: Commands,
+) {
+    let item = &trigger.item;
+
+    let Ok((agent, mut queue, hostility, is_selling)) = target_query.get_mut(trigger.target) else {
+        commands.trigger(InteractionEnded {
+            id: item.id,
+            source: trigger.source,
+            target: trigger.target,
+            reason: InteractionEndReason::PartnerGone,
+        });
+        return;
+    };
+
+    let (interacting, in_group) = busy_query.get(trigger.target).unwrap_or_default();
+    let busy = is_busy(
+        waiting_query.get(trigger.target).ok(),
+        interacting,
+        in_group,
+    );
+
+    let rejection = if hostility.is_some_and(|v| v.is_hostile_to(trigger.source)) {
+        Some(RejectionReason::Hostile)
+    } else if is_not_selling(item, agent, is_selling) {
+        Some(RejectionReason::NotSelling)
+    } else if busy && queue.len() >= config.max_queue_len {
+        Some(RejectionReason::Busy)
+    } else {
+        None
+    };
+
+    if let Some(reason) = rejection {
+        add_log_writer.send(AddLogEntry::new(
+            trigger.target,
+            format!("Rejected Interaction {}: {:?}", item.id, reason).as_str(),
+        ));
+
+        commands.trigger(InteractionEnded {
+            id: item.id,
+            source: trigger.source,
+            target: trigger.target,
+            reason: InteractionEndReason::Rejected(reason),
+        });
+        return;
+    }
+
+    queue.add(item.clone());
+
+    // the new request may outrank (and push out) one already waiting
+    let dropped = queue.overflow(config.max_queue_len);
+
+    if let Some(dropped) = &dropped {
+        add_log_writer.send(AddLogEntry::new(
+            trigger.target,
+            format!(
+                "Rejected Interaction {}: {:?}",
+                dropped.id,
+                RejectionReason::QueueTooLong
+            )
+            .as_str(),
+        ));
+
+        commands.trigger(InteractionEnded {
+            id: dropped.id,
+            source: dropped.source(),
+            target: trigger.target,
+            reason: InteractionEndReason::Rejected(RejectionReason::QueueTooLong),
+        });
+    }
+
+    if dropped.is_some_and(|v| v.id == item.id) {
+        return;
+    }
+
+    if let Ok(mut waiting) = waiting_query.get_mut(trigger.source) {
+        if waiting.id == item.id {
+            waiting.set_queued();
+        }
+    }
+}
+
+pub fn check_agent_interaction_queue_system(
+    mut query: Query<
+        (Entity, &Agent, &mut AgentInteractionQueue, Has<Selling>),
+        Without<Interacting>,
+    >,
+    waiting_query: Query<&WaitingInteraction>,
+    member_query: Query<(), With<GroupMember>>,
+    mut commands: Commands,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+) {
+    for (target_entity, agent, mut agent_interation_queue, is_selling) in &mut query {
+        if agent_interation_queue.is_empty() {
+            continue;
+        }
+
+        // its own request was accepted (it is about to interact elsewhere)
+        // or it is taking part in a group interaction: the requests wait
+        // until it is free again, or until they time out
+        if is_busy(
+            waiting_query.get(target_entity).ok(),
+            false,
+            member_query.contains(target_entity),
+        ) {
+            continue;
+        }
+
+        // requests it can't serve anymore are rejected on the way to the
+        // first one it can, the others wait: only ONE interaction at a time
+        // per agent
+        let next_item = std::iter::from_fn(|| agent_interation_queue.pop_first()).find(|item| {
+            if !is_not_selling(item, agent, is_selling) {
+                return true;
+            }
+
+            add_log_writer.send(AddLogEntry::new(
+                target_entity,
+                format!(
+                    "Rejected Interaction {}: {:?}",
+                    item.id,
+                    RejectionReason::NotSelling
+                )
+                .as_str(),
+            ));
+
+            commands.trigger(InteractionEnded {
+                id: item.id,
+                source: item.source(),
+                target: target_entity,
+                reason: InteractionEndReason::Rejected(RejectionReason::NotSelling),
+            });
+            false
+        });
+
+        let Some(interaction_item) = next_item else {
+            continue;
+        };
+
+        let source_entity = interaction_item.source();
+        let interaction = commands
+            .spawn(AgentInteraction::new(
+                interaction_item.id,
+                source_entity,
+                target_entity,
+            ))
+            .id();
+
+        match &interaction_item.kind {
+            AgentInteractionKind::Ask(sharing) => {
+                add_log_writer.send(AddLogEntry::new(
+                    target_entity,
+                    format!(
+                        "Received Ask Interaction. source {}. target: {}. Id: {}",
+                        sharing.source_name, sharing.target_name, interaction_item.id
+                    )
+                    .as_str(),
+                ));
+
+                commands.entity(target_entity).insert((
+                    sharing.clone(),
+                    Interacting::new(interaction, interaction_item.id),
+                ));
+            }
+            AgentInteractionKind::Trade(trade_negotiation) => {
+                add_log_writer.send(AddLogEntry::new(
+                    target_entity,
+                    format!("Received Trade Interaction {}", interaction_item.id).as_str(),
+                ));
+
+                commands.entity(target_entity).insert(TradeInteraction::new(
+                    *trade_negotiation,
+                    interaction,
+                    interaction_item.id,
+                ));
+            }
+            AgentInteractionKind::Hire(application) => {
+                add_log_writer.send(AddLogEntry::new(
+                    target_entity,
+                    format!("Received Hire Interaction {}", interaction_item.id).as_str(),
+                ));
+
+                commands.entity(target_entity).insert((
+                    application.clone(),
+                    Interacting::new(interaction, interaction_item.id),
+                ));
+            }
+        };
+
+        commands.trigger(InteractionStarted {
+            item: interaction_item,
+            target: source_entity,
+            accepted_by: target_entity,
+        });
+    }
+}
@@ -1,126 +1,703 @@
 use bevy::prelude::*;
 use std::{
     collections::{HashMap, HashSet},
-    sync::{Arc, RwLock},
+    hash::{Hash, Hasher},
 };
 
-use rand::Rng;
+use rand::{random, seq::SliceRandom};
 
-use crate::{core::item::ItemEnum, ecs::agent::Agent};
+use crate::{
+    core::item::ItemEnum,
+    ecs::{
+        agent::Agent, building::components::Resident, game_state::GameState, logs::AddLogEntry,
+        roles::seller::SellerRole,
+    },
+};
+
+// What an agent believes about a fact: how sure it is, how long ago it was
+// last confirmed and who told it, until it is checked
+#[derive(Debug, Clone, Copy)]
+pub struct Belief {
+    pub confidence: f32,
+    pub age_secs: f32,
+    pub source: Option<Entity>,
+    // contradicted since it was last learned or confirmed
+    pub doubted: bool,
+}
+
+impl Belief {
+    pub fn new(confidence: f32, source: Option<Entity>) -> Self {
+        Self {
+            confidence,
+            age_secs: 0.,
+            source,
+            doubted: false,
+        }
+    }
+}
 
-#[derive(Component)]
+// Chance for an agent to tell the truth when asked. Agents without it
+// never lie on purpose.
+#[derive(Component, Debug)]
+pub struct Honesty {
+    pub truthfulness: f32,
+}
+
+impl Honesty {
+    pub fn new(truthfulness: f32) -> Self {
+        Self { truthfulness }
+    }
+
+    pub fn lies(&self) -> bool {
+        random::<f32>() > self.truthfulness
+    }
+}
+
+// Only the ids of what the agent believes: the facts themselves live in
+// the SharedKnowledge resource, so queries just borrow it.
+#[derive(Component, Default)]
 pub struct AgentKnowledge {
-    base_knowledge: Arc<RwLock<BaseKnowledge>>,
-    known: HashSet<KnowledgeId>,
+    beliefs: HashMap<KnowledgeId, Belief>,
+    // believed facts about each item (sellers, prices, sites)
+    by_item: HashMap<ItemEnum, Vec<KnowledgeId>>,
+    // how much what others say is believed
+    trust: HashMap<Entity, f32>,
 }
 
 impl AgentKnowledge {
-    pub fn add(&mut self, id: KnowledgeId) {
-        self.known.insert(id);
-    }
-
-    pub fn get_sellers_of(&self, item: &ItemEnum) -> Vec<(Entity, KnowledgeId)> {
-        let mut sellers = vec![];
-        for id in self.known.iter() {
-            if let Some(knowledge) = self
-                .base_knowledge
-                .read()
-                .expect("fail to read on base knowledge")
-                .get_fact(&id)
-            {
-                if let KnowledgeFact::SellerInfo { wares, entity, .. } = knowledge {
-                    if wares.contains(item) {
-                        sellers.push((entity, id.clone()));
+    // Keeps the most confident version when the fact is already known
+    fn learn(
+        &mut self,
+        id: KnowledgeId,
+        confidence: f32,
+        source: Option<Entity>,
+        shared: &SharedKnowledge,
+    ) {
+        match self.beliefs.get_mut(&id) {
+            Some(belief) => {
+                if confidence >= belief.confidence {
+                    *belief = Belief::new(confidence, source);
+                }
+            }
+            None => {
+                self.beliefs.insert(id, Belief::new(confidence, source));
+
+                if let Some(fact) = shared.get_fact(&id) {
+                    for item in fact.items() {
+                        self.by_item.entry(*item).or_default().push(id);
                     }
                 }
             }
         }
-        sellers
+    }
+
+    fn forget(&mut self, id: &KnowledgeId) {
+        if self.beliefs.remove(id).is_some() {
+            for ids in self.by_item.values_mut() {
+                ids.retain(|known| known != id);
+            }
+        }
+    }
+
+    // Learns what the teller said, weighted by the trust in it. It may be
+    // misremembered: a wrong version of the fact is kept instead.
+    // Returns the id of the fact actually learned.
+    pub fn hear(
+        &mut self,
+        id: KnowledgeId,
+        teller: Entity,
+        teller_confidence: f32,
+        shared: &mut SharedKnowledge,
+        config: &KnowledgeConfig,
+    ) -> KnowledgeId {
+        let confidence = teller_confidence * config.hearsay_factor * self.trust_in(teller, config);
+
+        let id = if random::<f32>() < config.misremember_chance {
+            shared.misremember(id).unwrap_or(id)
+        } else {
+            id
+        };
+
+        self.learn(id, confidence, Some(teller), shared);
+        id
+    }
+
+    pub fn trust_in(&self, entity: Entity, config: &KnowledgeConfig) -> f32 {
+        self.trust
+            .get(&entity)
+            .cloned()
+            .unwrap_or(config.default_trust)
+    }
+
+    pub fn change_trust(&mut self, entity: Entity, delta: f32, config: &KnowledgeConfig) {
+        let trust = self.trust_in(entity, config);
+        self.trust.insert(entity, (trust + delta).clamp(0., 1.));
+    }
+
+    // Seen with its own eyes: whoever told it is trusted a bit more, and what
+    // the agent believed about the same thing (an older price) is outdated
+    pub fn confirm(&mut self, id: KnowledgeId, shared: &SharedKnowledge, config: &KnowledgeConfig) {
+        if let Some(source) = self.beliefs.get(&id).and_then(|v| v.source) {
+            self.change_trust(source, config.trust_gain, config);
+        }
+
+        if let Some(fact) = shared.get_fact(&id) {
+            let outdated: Vec<KnowledgeId> = self
+                .believed_facts(shared)
+                .filter(|(known_id, known)| *known_id != id && fact.supersedes(known))
+                .map(|(known_id, _)| known_id)
+                .collect();
+
+            for known_id in &outdated {
+                self.forget(known_id);
+            }
+        }
+
+        self.learn(id, 1., None, shared);
+    }
+
+    // Contradicted by facts: whoever told it is trusted a bit less. The same
+    // contradiction seen again (every observation of a missing seller) only
+    // counts once, until the fact is learned or confirmed again.
+    pub fn doubt(&mut self, id: &KnowledgeId, config: &KnowledgeConfig) {
+        let Some(belief) = self.beliefs.get_mut(id) else {
+            return;
+        };
+        if belief.doubted {
+            return;
+        }
+
+        belief.doubted = true;
+        belief.confidence *= config.contradiction_factor;
+
+        if let Some(source) = belief.source.take() {
+            self.change_trust(source, -config.trust_loss, config);
+        }
+    }
+
+    pub fn knows(&self, id: &KnowledgeId) -> bool {
+        self.beliefs.contains_key(id)
+    }
+
+    pub fn confidence_of(&self, id: &KnowledgeId) -> f32 {
+        self.beliefs
+            .get(id)
+            .map(|v| v.confidence)
+            .unwrap_or_default()
+    }
+
+    pub fn known(&self) -> impl Iterator<Item = &KnowledgeId> {
+        self.beliefs.keys()
+    }
+
+    // Facts known by this agent and not by the other one, of any kind
+    pub fn unknown_to(&self, other: &AgentKnowledge) -> Vec<KnowledgeId> {
+        self.beliefs
+            .keys()
+            .filter(|id| !other.knows(id))
+            .cloned()
+            .collect()
+    }
+
+    // Beliefs get stale over time, the ones nobody is sure about anymore are
+    // forgotten. Returns how many were forgotten.
+    pub fn age(&mut self, delta: f32, config: &KnowledgeConfig) -> usize {
+        let mut forgotten = vec![];
+
+        for (id, belief) in self.beliefs.iter_mut() {
+            belief.age_secs += delta;
+            if belief.age_secs > config.stale_after_secs {
+                belief.confidence -= config.decay_per_sec * delta;
+            }
+            if belief.confidence < config.forget_below {
+                forgotten.push(*id);
+            }
+        }
+
+        for id in &forgotten {
+            self.forget(id);
+        }
+
+        forgotten.len()
+    }
+
+    fn facts_about<'a>(
+        &'a self,
+        item: &ItemEnum,
+        shared: &'a SharedKnowledge,
+    ) -> impl Iterator<Item = (KnowledgeId, &'a KnowledgeFact)> + 'a {
+        self.by_item
+            .get(item)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| shared.get_fact(id).map(|fact| (*id, fact)))
+    }
+
+    fn believed_facts<'a>(
+        &'a self,
+        shared: &'a SharedKnowledge,
+    ) -> impl Iterator<Item = (KnowledgeId, &'a KnowledgeFact)> + 'a {
+        self.beliefs
+            .keys()
+            .filter_map(|id| shared.get_fact(id).map(|fact| (*id, fact)))
+    }
+
+    pub fn get_sellers_of<'a>(
+        &'a self,
+        item: &ItemEnum,
+        shared: &'a SharedKnowledge,
+    ) -> impl Iterator<Item = (Entity, KnowledgeId)> + 'a {
+        self.get_seller_locations_of(item, shared)
+            .map(|(entity, _, id)| (entity, id))
+    }
+
+    pub fn get_seller_locations_of<'a>(
+        &'a self,
+        item: &ItemEnum,
+        shared: &'a SharedKnowledge,
+    ) -> impl Iterator<Item = (Entity, Vec3, KnowledgeId)> + 'a {
+        self.facts_about(item, shared)
+            .filter_map(|(id, fact)| match fact {
+                KnowledgeFact::SellerInfo { entity, shop, .. } => shared
+                    .landmark(*shop)
+                    .map(|location| (*entity, location, id)),
+                _ => None,
+            })
+    }
+
+    // Every fact saying that the seller sells the item
+    pub fn facts_about_seller<'a>(
+        &'a self,
+        seller: Entity,
+        item: &ItemEnum,
+        shared: &'a SharedKnowledge,
+    ) -> impl Iterator<Item = KnowledgeId> + 'a {
+        self.get_sellers_of(item, shared)
+            .filter(move |(entity, _)| *entity == seller)
+            .map(|(_, id)| id)
+    }
+
+    // Last price per unit seen (or heard of) at the seller, the most
+    // trusted one when several are known
+    pub fn get_price_at(
+        &self,
+        seller: Entity,
+        item: &ItemEnum,
+        shared: &SharedKnowledge,
+    ) -> Option<f32> {
+        self.facts_about(item, shared)
+            .filter_map(|(id, fact)| match fact {
+                KnowledgeFact::PriceInfo {
+                    seller: entity,
+                    unit_price,
+                    ..
+                } if *entity == seller => Some((self.confidence_of(&id), *unit_price)),
+                _ => None,
+            })
+            .max_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, unit_price)| unit_price)
+    }
+
+    pub fn get_resource_sites_of<'a>(
+        &'a self,
+        item: &ItemEnum,
+        shared: &'a SharedKnowledge,
+    ) -> impl Iterator<Item = (Vec3, KnowledgeId)> + 'a {
+        self.facts_about(item, shared)
+            .filter_map(|(id, fact)| match fact {
+                KnowledgeFact::ResourceSite { location, .. } => Some((*location, id)),
+                _ => None,
+            })
+    }
+
+    pub fn get_home_of(&self, owner: Entity, shared: &SharedKnowledge) -> Option<Vec3> {
+        self.believed_facts(shared)
+            .filter_map(|(id, fact)| match fact {
+                KnowledgeFact::Home {
+                    owner: home_owner,
+                    building,
+                } if *home_owner == owner => shared
+                    .landmark(*building)
+                    .map(|location| (self.confidence_of(&id), location)),
+                _ => None,
+            })
+            .max_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, location)| location)
+    }
+
+    pub fn get_markets<'a>(
+        &'a self,
+        shared: &'a SharedKnowledge,
+    ) -> impl Iterator<Item = (Vec3, KnowledgeId)> + 'a {
+        self.believed_facts(shared)
+            .filter_map(|(id, fact)| match fact {
+                KnowledgeFact::Market { building } => {
+                    shared.landmark(*building).map(|location| (location, id))
+                }
+                _ => None,
+            })
+    }
+
+    // Who the debtor owes money to, and how much. Debts paid back or written
+    // off are known as owing nothing.
+    pub fn get_debts_of<'a>(
+        &'a self,
+        debtor: Entity,
+        shared: &'a SharedKnowledge,
+    ) -> impl Iterator<Item = (Entity, usize)> + 'a {
+        self.believed_facts(shared)
+            .filter_map(move |(_, fact)| match fact {
+                KnowledgeFact::Debt {
+                    debtor: entity,
+                    creditor,
+                    amount,
+                } if *entity == debtor && *amount > 0 => Some((*creditor, *amount)),
+                _ => None,
+            })
+    }
+
+    // Cheapest and closest sellers first, weighted by how sure the agent is
+    // about them. Walking there costs time, worth `time_cost` a second at the
+    // agent `speed`. Unknown prices count as the most expensive one known.
+    pub fn rank_sellers_of(
+        &self,
+        item: &ItemEnum,
+        from: Vec3,
+        speed: f32,
+        shared: &SharedKnowledge,
+        config: &KnowledgeConfig,
+    ) -> Vec<(Entity, KnowledgeId)> {
+        let worst_price = self
+            .facts_about(item, shared)
+            .filter_map(|(_, fact)| match fact {
+                KnowledgeFact::PriceInfo { unit_price, .. } => Some(*unit_price),
+                _ => None,
+            })
+            .fold(0., f32::max);
+
+        let mut ranked: Vec<(f32, Entity, KnowledgeId)> = self
+            .get_seller_locations_of(item, shared)
+            .map(|(seller, location, id)| {
+                let cost = location.distance(from) / speed.max(f32::EPSILON) * config.time_cost
+                    + self
+                        .get_price_at(seller, item, shared)
+                        .unwrap_or(worst_price);
+                (cost / self.confidence_of(&id).max(f32::EPSILON), seller, id)
+            })
+            .collect();
+
+        ranked.sort_by(|(a, _, _), (b, _, _)| a.total_cmp(b));
+        ranked
+            .into_iter()
+            .map(|(_, seller, id)| (seller, id))
+            .collect()
+    }
+
+    // Seen with its own eyes: the fact is true, and confirmed
+    pub fn witness(
+        &mut self,
+        fact: KnowledgeFact,
+        shared: &mut SharedKnowledge,
+        config: &KnowledgeConfig,
+    ) -> KnowledgeId {
+        let id = shared.add_fact(fact);
+        self.confirm(id, shared, config);
+        id
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum KnowledgeFact {
     SellerInfo {
         entity: Entity,
         // name: String,
-        location: Vec3,
+        // where they sell from
+        shop: Entity,
         // We need to know what they sell to answer "where can I buy water?"
         wares: Vec<ItemEnum>,
     },
+    PriceInfo {
+        seller: Entity,
+        item: ItemEnum,
+        unit_price: f32,
+    },
+    ResourceSite {
+        location: Vec3,
+        item: ItemEnum,
+    },
+    Home {
+        owner: Entity,
+        building: Entity,
+    },
+    Market {
+        building: Entity,
+    },
+    Debt {
+        debtor: Entity,
+        creditor: Entity,
+        amount: usize,
+    },
     // You could add other facts later, like:
     Recipe {
         output: ItemEnum,
         ingredients: Vec<ItemEnum>,
     },
-    // PointOfInterest { name: String, location: Vec3 },
 }
 
-pub type KnowledgeId = u32;
+impl KnowledgeFact {
+    // Items the fact is about, to index it
+    pub fn items(&self) -> &[ItemEnum] {
+        match self {
+            KnowledgeFact::SellerInfo { wares, .. } => wares,
+            KnowledgeFact::PriceInfo { item, .. } | KnowledgeFact::ResourceSite { item, .. } => {
+                std::slice::from_ref(item)
+            }
+            KnowledgeFact::Recipe { output, .. } => std::slice::from_ref(output),
+            _ => &[],
+        }
+    }
 
-/// The singleton knowledge base content
-#[derive(Debug)]
-pub struct BaseKnowledge {
-    facts: HashMap<KnowledgeId, KnowledgeFact>,
-    next_id: u32,
+    // Both facts are about the same thing, only one can be true at a time
+    pub fn supersedes(&self, other: &KnowledgeFact) -> bool {
+        match (self, other) {
+            (
+                KnowledgeFact::PriceInfo { seller, item, .. },
+                KnowledgeFact::PriceInfo {
+                    seller: other_seller,
+                    item: other_item,
+                    ..
+                },
+            ) => seller == other_seller && item == other_item,
+            (
+                KnowledgeFact::Home { owner, .. },
+                KnowledgeFact::Home {
+                    owner: other_owner, ..
+                },
+            ) => owner == other_owner,
+            (
+                KnowledgeFact::Debt {
+                    debtor, creditor, ..
+                },
+                KnowledgeFact::Debt {
+                    debtor: other_debtor,
+                    creditor: other_creditor,
+                    ..
+                },
+            ) => debtor == other_debtor && creditor == other_creditor,
+            _ => false,
+        }
+    }
 }
 
-impl BaseKnowledge {
-    pub fn new() -> Self {
-        Self {
-            facts: HashMap::new(),
-            next_id: 0,
+// Facts are looked up by value in SharedKnowledge. Prices and locations are
+// hashed through their bits, with -0. and 0. hashed alike as they are equal.
+impl Eq for KnowledgeFact {}
+
+impl Hash for KnowledgeFact {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        fn hash_f32<H: Hasher>(value: f32, state: &mut H) {
+            (value + 0.).to_bits().hash(state);
+        }
+
+        std::mem::discriminant(self).hash(state);
+        match self {
+            KnowledgeFact::SellerInfo {
+                entity,
+                shop,
+                wares,
+            } => {
+                entity.hash(state);
+                shop.hash(state);
+                wares.hash(state);
+            }
+            KnowledgeFact::PriceInfo {
+                seller,
+                item,
+                unit_price,
+            } => {
+                seller.hash(state);
+                item.hash(state);
+                hash_f32(*unit_price, state);
+            }
+            KnowledgeFact::ResourceSite { location, item } => {
+                for coordinate in location.to_array() {
+                    hash_f32(coordinate, state);
+                }
+                item.hash(state);
+            }
+            KnowledgeFact::Home { owner, building } => {
+                owner.hash(state);
+                building.hash(state);
+            }
+            KnowledgeFact::Market { building } => building.hash(state),
+            KnowledgeFact::Debt {
+                debtor,
+                creditor,
+                amount,
+            } => {
+                debtor.hash(state);
+                creditor.hash(state);
+                amount.hash(state);
+            }
+            KnowledgeFact::Recipe {
+                output,
+                ingredients,
+            } => {
+                output.hash(state);
+                ingredients.hash(state);
+            }
         }
     }
+}
 
+pub type KnowledgeId = u32;
+
+// Every fact ever known by someone, true or not, shared by all agents.
+// A fact keeps its id, its index, once added, and never changes: a new price
+// at a seller is a new fact, each agent replacing only its own belief about
+// the older one.
+#[derive(Resource, Debug, Default)]
+pub struct SharedKnowledge {
+    facts: Vec<KnowledgeFact>,
+    ids: HashMap<KnowledgeFact, KnowledgeId>,
+    // made up or misremembered facts that never matched the ground truth
+    false_facts: HashSet<KnowledgeId>,
+    // buildings are landmarks anyone can find, facts point at them
+    landmarks: HashMap<Entity, Vec3>,
+}
+
+impl SharedKnowledge {
+    // Identical facts share the same id. Facts added here are true, even if
+    // they were made up before.
     pub fn add_fact(&mut self, fact: KnowledgeFact) -> KnowledgeId {
-        // TODO: check if fact already exists
-        self.facts.insert(self.next_id, fact);
-        self.next_id += 1;
-        self.next_id
+        let id = self.insert_fact(fact);
+        self.false_facts.remove(&id);
+        id
+    }
+
+    pub fn add_false_fact(&mut self, fact: KnowledgeFact) -> KnowledgeId {
+        if let Some(id) = self.find_fact(&fact) {
+            return id;
+        }
+
+        let id = self.insert_fact(fact);
+        self.false_facts.insert(id);
+        id
     }
 
-    // A method to look up the content of a fact by its ID.
-    pub fn get_fact(&self, id: &KnowledgeId) -> Option<KnowledgeFact> {
-        match self.facts.get(id) {
-            Some(v) => Some(v.clone()),
-            None => None,
+    fn insert_fact(&mut self, fact: KnowledgeFact) -> KnowledgeId {
+        if let Some(id) = self.find_fact(&fact) {
+            return id;
         }
+
+        self.facts.push(fact.clone());
+        let id = (self.facts.len() - 1) as KnowledgeId;
+        self.ids.insert(fact, id);
+        id
     }
-}
 
-#[derive(Resource, Clone)]
-pub struct SharedKnowledge(Arc<RwLock<BaseKnowledge>>);
+    pub fn find_fact(&self, fact: &KnowledgeFact) -> Option<KnowledgeId> {
+        self.ids.get(fact).copied()
+    }
 
-impl SharedKnowledge {
-    pub fn clone_base_knowledge(&self) -> Arc<RwLock<BaseKnowledge>> {
-        self.0.clone()
+    pub fn get_fact(&self, id: &KnowledgeId) -> Option<&KnowledgeFact> {
+        self.facts.get(*id as usize)
     }
 
-    pub fn add_fact(&mut self, fact: KnowledgeFact) -> KnowledgeId {
-        self.0
-            .write()
-            .expect("fail to write on base knowledge")
-            .add_fact(fact)
+    pub fn is_false(&self, id: &KnowledgeId) -> bool {
+        self.false_facts.contains(id)
+    }
+
+    // A wrong version of the fact (the seller sells something else)
+    pub fn misremember(&mut self, id: KnowledgeId) -> Option<KnowledgeId> {
+        let KnowledgeFact::SellerInfo {
+            entity,
+            shop,
+            wares,
+        } = self.get_fact(&id)?
+        else {
+            return None;
+        };
+
+        let other_wares: Vec<ItemEnum> = ItemEnum::ALL
+            .into_iter()
+            .filter(|item| *item != ItemEnum::MONEY && !wares.contains(item))
+            .collect();
+        let wrong_ware = other_wares.choose(&mut rand::thread_rng())?;
+
+        let fact = KnowledgeFact::SellerInfo {
+            entity: *entity,
+            shop: *shop,
+            wares: vec![*wrong_ware],
+        };
+        Some(self.add_false_fact(fact))
+    }
+
+    pub fn add_landmark(&mut self, building: Entity, location: Vec3) {
+        self.landmarks.insert(building, location);
+    }
+
+    pub fn landmark(&self, building: Entity) -> Option<Vec3> {
+        self.landmarks.get(&building).copied()
     }
 
     pub fn get_all(&self) -> impl Iterator<Item = KnowledgeId> {
-        let knowledge_lock = self.0.read().expect("fail to read on base_knowledge");
-        let keys_vec: Vec<KnowledgeId> = knowledge_lock.facts.keys().cloned().collect();
-        keys_vec.into_iter()
-    }
-
-    pub fn get_one_random(&self) -> KnowledgeId {
-        let mut rnd = rand::thread_rng();
-        let knowledge_lock = self.0.read().expect("fail to read on base_knowledge");
-        let max = knowledge_lock.facts.len();
-        let idx = rnd.gen_range(0..max);
-        knowledge_lock
-            .facts
-            .keys()
-            .nth(idx)
-            .expect("Fail to get one random knowledge on base knowledge")
-            .clone()
+        0..self.facts.len() as KnowledgeId
+    }
+
+    // A random true fact among the ones `accept`ed
+    pub fn get_one_random(&self, accept: impl Fn(&KnowledgeFact) -> bool) -> Option<KnowledgeId> {
+        let candidates: Vec<KnowledgeId> = self
+            .get_all()
+            .filter(|id| !self.is_false(id) && self.get_fact(id).is_some_and(&accept))
+            .collect();
+        candidates.choose(&mut rand::thread_rng()).copied()
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct KnowledgeConfig {
+    // confidence kept when a fact is heard from someone else
+    pub hearsay_factor: f32,
+    // confidence kept when a fact is contradicted (seller not found, out of stock)
+    pub contradiction_factor: f32,
+    // trust in agents never met, and how it moves when what they said turns
+    // out to be true or false
+    pub default_trust: f32,
+    pub trust_gain: f32,
+    pub trust_loss: f32,
+    // max facts told by one agent in a single conversation
+    pub bandwidth: usize,
+    // chance to keep a wrong version of a fact heard from someone
+    pub misremember_chance: f32,
+    // money a second of walking is worth when choosing where to buy
+    pub time_cost: f32,
+    // beliefs not confirmed for this long start losing confidence
+    pub stale_after_secs: f32,
+    pub decay_per_sec: f32,
+    pub forget_below: f32,
+    // agents check what they believe about places closer than this
+    pub observation_radius: f32,
+    pub observation_check_secs: f32,
+}
+
+impl Default for KnowledgeConfig {
+    fn default() -> Self {
+        Self {
+            hearsay_factor: 0.8,
+            contradiction_factor: 0.5,
+            default_trust: 0.8,
+            trust_gain: 0.05,
+            trust_loss: 0.2,
+            bandwidth: 3,
+            misremember_chance: 0.05,
+            time_cost: 1.25,
+            stale_after_secs: 60.,
+            decay_per_sec: 0.01,
+            forget_below: 0.1,
+            observation_radius: 60.,
+            observation_check_secs: 1.,
+        }
     }
 }
 
@@ -129,23 +706,142 @@ pub struct KnowledgePlugin;
 
 impl Plugin for KnowledgePlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(SharedKnowledge(Arc::new(RwLock::new(BaseKnowledge::new()))));
+        app.init_resource::<SharedKnowledge>()
+            .init_resource::<KnowledgeConfig>();
 
         // Add systems to inject knowledge and allow agents to use it
-        app.add_systems(Update, attach_agent_knowledge);
+        app.add_systems(Update, attach_agent_knowledge).add_systems(
+            Update,
+            (knowledge_aging_system, observe_sellers_system).run_if(in_state(GameState::Running)),
+        );
     }
 }
 
 fn attach_agent_knowledge(
     mut commands: Commands,
-    shared: Res<SharedKnowledge>,
+    mut shared: ResMut<SharedKnowledge>,
+    config: Res<KnowledgeConfig>,
     query: Query<Entity, (With<Agent>, Without<AgentKnowledge>)>, // example: any named entity
+    resident_query: Query<&Resident>,
 ) {
     for entity in &query {
-        commands.entity(entity).insert(AgentKnowledge {
-            base_knowledge: shared.clone_base_knowledge(),
-            // known: HashSet::from_iter(shared.get_all()),
-            known: HashSet::from([shared.get_one_random()]),
-        });
+        let mut knowledge = AgentKnowledge::default();
+        // a seller anyone could have come across, not someone else's home,
+        // debts or prices
+        if let Some(id) =
+            shared.get_one_random(|fact| matches!(fact, KnowledgeFact::SellerInfo { .. }))
+        {
+            knowledge.confirm(id, &shared, &config);
+        }
+        // Everyone knows where they live
+        if let Ok(resident) = resident_query.get(entity) {
+            knowledge.witness(
+                KnowledgeFact::Home {
+                    owner: entity,
+                    building: resident.home,
+                },
+                &mut shared,
+                &config,
+            );
+        }
+        commands.entity(entity).insert(knowledge);
+    }
+}
+
+fn knowledge_aging_system(
+    mut query: Query<(Entity, &mut AgentKnowledge)>,
+    config: Res<KnowledgeConfig>,
+    time: Res<Time>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+) {
+    for (entity, mut knowledge) in &mut query {
+        let forgotten = knowledge.age(time.delta_secs(), &config);
+
+        if forgotten > 0 {
+            add_log_writer.send(AddLogEntry::new(
+                entity,
+                format!("Knowledge -> forgot {} stale facts", forgotten).as_str(),
+            ));
+        }
+    }
+}
+
+// Agents close to where they believe a seller is check it with their own
+// eyes: the belief is confirmed when the seller is there with the wares in
+// stock and doubted otherwise (moved, ran out, gone). Sellers in sight are
+// learned as they are now.
+fn observe_sellers_system(
+    mut agent_query: Query<(&Transform, &mut AgentKnowledge)>,
+    seller_query: Query<(Entity, &Transform, &Agent, &SellerRole)>,
+    mut shared: ResMut<SharedKnowledge>,
+    config: Res<KnowledgeConfig>,
+    time: Res<Time>,
+    mut since_last_check: Local<f32>,
+) {
+    *since_last_check += time.delta_secs();
+    if *since_last_check < config.observation_check_secs {
+        return;
     }
+    *since_last_check = 0.;
+
+    let mut sellers: Vec<(Entity, Vec3, Option<KnowledgeId>)> = vec![];
+    for (entity, transform, agent, seller_role) in &seller_query {
+        let wares: Vec<ItemEnum> = ItemEnum::ALL
+            .into_iter()
+            .filter(|item| *item != ItemEnum::MONEY && agent.inventory.get_qty(*item) > 0)
+            .collect();
+
+        let current = if wares.is_empty() {
+            None
+        } else {
+            Some(shared.add_fact(KnowledgeFact::SellerInfo {
+                entity,
+                shop: seller_role.shop,
+                wares,
+            }))
+        };
+
+        sellers.push((entity, transform.translation, current));
+    }
+
+    let shared = shared.into_inner();
+
+    agent_query
+        .par_iter_mut()
+        .for_each(|(transform, mut knowledge)| {
+            let position = transform.translation;
+            let in_sight =
+                |location: Vec3| location.distance(position) <= config.observation_radius;
+
+            let checked: Vec<(KnowledgeId, bool)> = knowledge
+                .believed_facts(shared)
+                .filter_map(|(id, fact)| match fact {
+                    KnowledgeFact::SellerInfo { entity, shop, .. }
+                        if shared.landmark(*shop).is_some_and(in_sight) =>
+                    {
+                        let seen = sellers.iter().any(|(seller, seller_position, current)| {
+                            seller == entity && in_sight(*seller_position) && *current == Some(id)
+                        });
+                        Some((id, seen))
+                    }
+                    _ => None,
+                })
+                .collect();
+
+            for (id, seen) in checked {
+                if seen {
+                    knowledge.confirm(id, shared, &config);
+                } else {
+                    knowledge.doubt(&id, &config);
+                }
+            }
+
+            for (_, seller_position, current) in &sellers {
+                if let Some(id) = current {
+                    if in_sight(*seller_position) {
+                        knowledge.confirm(*id, shared, &config);
+                    }
+                }
+            }
+        });
 }
@@ -6,7 +6,7 @@ use bevy::ecs::{component::Component, entity::Entity, event::Event};
 pub struct LogEntry {
     pub description: String,
     pub time: Duration,
-    pub frame: u32
+    pub frame: u32,
 }
 
 #[derive(Component)]
@@ -33,7 +33,7 @@ impl AgentLogs {
         self.logs.push(LogEntry {
             description: description.clone(),
             time: SystemTime::now().duration_since(UNIX_EPOCH).ok().unwrap(),
-            frame
+            frame,
         });
 
         if self.logs.len() > 100 && self.logs.len() % 100 == 0 {
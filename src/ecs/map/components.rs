@@ -0,0 +1,29 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+// Planned route of a Walking agent, consumed waypoint by waypoint. The last
+// waypoint is the destination itself.
+#[derive(Component, Debug)]
+pub struct Path {
+    // the Walking destination this path was planned for
+    pub destination: Vec3,
+    waypoints: VecDeque<Vec3>,
+}
+
+impl Path {
+    pub fn new(destination: Vec3, waypoints: Vec<Vec3>) -> Self {
+        Self {
+            destination,
+            waypoints: waypoints.into(),
+        }
+    }
+
+    pub fn next(&self) -> Option<Vec3> {
+        self.waypoints.front().copied()
+    }
+
+    pub fn advance(&mut self) {
+        self.waypoints.pop_front();
+    }
+}
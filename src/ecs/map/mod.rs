@@ -0,0 +1,6 @@
+pub mod components;
+pub mod pathfinding;
+pub mod plugin;
+pub mod resources;
+mod systems;
+pub mod terrain;
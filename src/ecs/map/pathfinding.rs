@@ -0,0 +1,130 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::ecs::map::{
+    components::Path,
+    resources::{PathCache, WorldMap},
+    terrain::MIN_MOVEMENT_COST,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Open {
+    estimate: f32,
+    tile: UVec2,
+}
+
+impl Eq for Open {}
+
+// Reversed, so the BinaryHeap pops the lowest estimate first
+impl Ord for Open {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+impl PartialOrd for Open {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn heuristic(from: UVec2, to: UVec2) -> f32 {
+    let d = (from.as_ivec2() - to.as_ivec2()).abs().as_vec2();
+    (d.max_element() + (std::f32::consts::SQRT_2 - 1.) * d.min_element()) * MIN_MOVEMENT_COST
+}
+
+// A* over the tiles, from `from` (included) to `to` (included)
+pub fn find_path(map: &WorldMap, from: UVec2, to: UVec2) -> Option<Vec<UVec2>> {
+    let mut open = BinaryHeap::new();
+    let mut came_from: HashMap<UVec2, UVec2> = HashMap::new();
+    let mut cost_so_far: HashMap<UVec2, f32> = HashMap::new();
+
+    open.push(Open {
+        estimate: heuristic(from, to),
+        tile: from,
+    });
+    cost_so_far.insert(from, 0.);
+
+    while let Some(Open { tile, .. }) = open.pop() {
+        if tile == to {
+            let mut path = vec![tile];
+            let mut current = tile;
+            while let Some(previous) = came_from.get(&current) {
+                path.push(*previous);
+                current = *previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        let cost = cost_so_far[&tile];
+        for (next, step_cost) in map.neighbours(tile) {
+            let next_cost = cost + step_cost;
+            if cost_so_far
+                .get(&next)
+                .is_some_and(|known| *known <= next_cost)
+            {
+                continue;
+            }
+
+            cost_so_far.insert(next, next_cost);
+            came_from.insert(next, tile);
+            open.push(Open {
+                estimate: next_cost + heuristic(next, to),
+                tile: next,
+            });
+        }
+    }
+
+    None
+}
+
+// Tile centers to walk through, skipping the ones a straight line can cut
+// across. The starting tile is left out.
+fn smooth(map: &WorldMap, tiles: &[UVec2]) -> Vec<Vec3> {
+    let points: Vec<Vec3> = tiles.iter().map(|tile| map.tile_center(*tile)).collect();
+
+    let mut waypoints = vec![];
+    let mut anchor = 0;
+    while anchor + 1 < points.len() {
+        let mut next = anchor + 1;
+        while next + 1 < points.len() && map.is_clear_line(points[anchor], points[next + 1]) {
+            next += 1;
+        }
+        waypoints.push(points[next]);
+        anchor = next;
+    }
+
+    waypoints
+}
+
+// Route from `from` to `to`, or to the closest reachable point when `to` is
+// not passable. None when there is no way there.
+pub fn plan_path(map: &WorldMap, cache: &mut PathCache, from: Vec3, to: Vec3) -> Option<Path> {
+    let start = map.closest_tile(from);
+    let goal = map.nearest_passable(map.closest_tile(to))?;
+    let destination = if map.tile_at(to) == Some(goal) {
+        to
+    } else {
+        map.tile_center(goal)
+    };
+
+    let waypoints = match cache.get(start, goal) {
+        Some(waypoints) => waypoints.clone(),
+        None => {
+            let waypoints = smooth(map, &find_path(map, start, goal)?);
+            cache.insert(start, goal, waypoints.clone());
+            waypoints
+        }
+    };
+
+    // The goal tile center is replaced by the exact destination
+    let mut waypoints: Vec<Vec3> = waypoints
+        .into_iter()
+        .filter(|waypoint| map.tile_at(*waypoint) != Some(goal))
+        .collect();
+    waypoints.push(destination);
+
+    Some(Path::new(destination, waypoints))
+}
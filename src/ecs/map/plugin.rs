@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+
+use crate::ecs::{
+    game_state::GameState,
+    map::{
+        resources::{MapConfig, PathCache, WorldMap},
+        systems::*,
+    },
+};
+
+pub struct MapPlugin;
+
+impl Plugin for MapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MapConfig>();
+
+        // Loaded right away, before any Startup system places agents on it
+        let config = app.world().resource::<MapConfig>().clone();
+        let map = WorldMap::load(&config.path, config.tile_size).unwrap_or_else(|e| {
+            error!("Could not load the world map ({}), using a flat one", e);
+            WorldMap::flat(40, 40, config.tile_size)
+        });
+
+        app.insert_resource(map)
+            .insert_resource(PathCache::new(config.path_cache_size))
+            .add_systems(Startup, spawn_map_tiles)
+            .add_systems(
+                Update,
+                plan_paths_system.run_if(in_state(GameState::Running)),
+            )
+            .add_observer(remove_path_with_walking);
+    }
+}
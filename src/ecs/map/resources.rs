@@ -0,0 +1,365 @@
+use std::collections::VecDeque;
+
+use bevy::{prelude::*, utils::HashMap};
+use rand::{seq::SliceRandom, Rng};
+
+use crate::ecs::map::terrain::Terrain;
+
+#[derive(Resource, Debug, Clone)]
+pub struct MapConfig {
+    // text file, one character per tile, first line is the northern edge
+    pub path: String,
+    pub tile_size: f32,
+    // planned routes kept for reuse, oldest dropped first
+    pub path_cache_size: usize,
+}
+
+impl Default for MapConfig {
+    fn default() -> Self {
+        Self {
+            path: "assets/world.map".to_string(),
+            tile_size: 25.,
+            path_cache_size: 256,
+        }
+    }
+}
+
+// Tile grid centered on the world origin. Plain data, so the movement
+// systems can use it without anything being rendered.
+#[derive(Resource, Debug, Clone)]
+pub struct WorldMap {
+    pub width: u32,
+    pub height: u32,
+    pub tile_size: f32,
+    // row by row, starting from the northern edge
+    tiles: Vec<Terrain>,
+    passable: Vec<UVec2>,
+}
+
+impl WorldMap {
+    pub fn new(width: u32, height: u32, tile_size: f32, tiles: Vec<Terrain>) -> Self {
+        let passable = (0..height)
+            .flat_map(|y| (0..width).map(move |x| UVec2::new(x, y)))
+            .filter(|tile| tiles[(tile.y * width + tile.x) as usize].is_passable())
+            .collect();
+
+        Self {
+            width,
+            height,
+            tile_size,
+            tiles,
+            passable,
+        }
+    }
+
+    // All grass, used when no map file could be read
+    pub fn flat(width: u32, height: u32, tile_size: f32) -> Self {
+        Self::new(
+            width,
+            height,
+            tile_size,
+            vec![Terrain::Grass; (width * height) as usize],
+        )
+    }
+
+    pub fn load(path: &str, tile_size: f32) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        Self::parse(&text, tile_size).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    pub fn parse(text: &str, tile_size: f32) -> Result<Self, String> {
+        let rows: Vec<&str> = text
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        let width = rows.first().map(|row| row.chars().count()).unwrap_or(0);
+        if width == 0 {
+            return Err("empty map".to_string());
+        }
+
+        let mut tiles = Vec::with_capacity(width * rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            if row.chars().count() != width {
+                return Err(format!("line {} is not {} tiles wide", y + 1, width));
+            }
+            for (x, c) in row.chars().enumerate() {
+                let terrain = Terrain::from_char(c).ok_or_else(|| {
+                    format!(
+                        "unknown terrain '{}' at line {}, column {}",
+                        c,
+                        y + 1,
+                        x + 1
+                    )
+                })?;
+                tiles.push(terrain);
+            }
+        }
+
+        Ok(Self::new(width as u32, rows.len() as u32, tile_size, tiles))
+    }
+
+    // World position of the south-west corner
+    fn origin(&self) -> Vec2 {
+        -Vec2::new(self.width as f32, self.height as f32) * self.tile_size / 2.
+    }
+
+    pub fn tile_at(&self, position: Vec3) -> Option<UVec2> {
+        let local = (position.truncate() - self.origin()) / self.tile_size;
+        if local.x < 0. || local.y < 0. {
+            return None;
+        }
+
+        let x = local.x as u32;
+        let from_south = local.y as u32;
+        if x >= self.width || from_south >= self.height {
+            return None;
+        }
+
+        Some(UVec2::new(x, self.height - 1 - from_south))
+    }
+
+    // Like tile_at, but positions outside the map get the closest edge tile
+    pub fn closest_tile(&self, position: Vec3) -> UVec2 {
+        let local = ((position.truncate() - self.origin()) / self.tile_size).floor();
+        let x = local.x.clamp(0., (self.width - 1) as f32) as u32;
+        let from_south = local.y.clamp(0., (self.height - 1) as f32) as u32;
+        UVec2::new(x, self.height - 1 - from_south)
+    }
+
+    pub fn tile_center(&self, tile: UVec2) -> Vec3 {
+        let from_south = self.height - 1 - tile.y;
+        let center =
+            self.origin() + (Vec2::new(tile.x as f32, from_south as f32) + 0.5) * self.tile_size;
+        center.extend(0.)
+    }
+
+    pub fn terrain(&self, tile: UVec2) -> Terrain {
+        self.tiles[(tile.y * self.width + tile.x) as usize]
+    }
+
+    pub fn terrain_at(&self, position: Vec3) -> Option<Terrain> {
+        self.tile_at(position).map(|tile| self.terrain(tile))
+    }
+
+    // Outside the map counts as impassable
+    pub fn is_passable(&self, position: Vec3) -> bool {
+        self.terrain_at(position)
+            .is_some_and(|terrain| terrain.is_passable())
+    }
+
+    pub fn movement_cost(&self, position: Vec3) -> Option<f32> {
+        self.terrain_at(position)
+            .and_then(|terrain| terrain.movement_cost())
+    }
+
+    pub fn tile_cost(&self, tile: UVec2) -> Option<f32> {
+        self.terrain(tile).movement_cost()
+    }
+
+    // Passable tiles around `tile` with the cost of stepping onto them.
+    // Diagonal steps are not allowed to cut the corner of an obstacle.
+    pub fn neighbours(&self, tile: UVec2) -> impl Iterator<Item = (UVec2, f32)> + '_ {
+        let here = self.tile_cost(tile).unwrap_or(1.);
+        let at = move |dx: i32, dy: i32| {
+            let next = tile.as_ivec2() + IVec2::new(dx, dy);
+            (next.x >= 0
+                && next.y >= 0
+                && (next.x as u32) < self.width
+                && (next.y as u32) < self.height)
+                .then(|| next.as_uvec2())
+                .and_then(|next| self.tile_cost(next).map(|cost| (next, cost)))
+        };
+
+        [
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (-1, 0),
+            (1, 0),
+            (-1, 1),
+            (0, 1),
+            (1, 1),
+        ]
+        .into_iter()
+        .filter_map(move |(dx, dy)| {
+            let (next, cost) = at(dx, dy)?;
+            if dx != 0 && dy != 0 && (at(dx, 0).is_none() || at(0, dy).is_none()) {
+                return None;
+            }
+            let length = if dx != 0 && dy != 0 {
+                std::f32::consts::SQRT_2
+            } else {
+                1.
+            };
+            Some((next, length * (here + cost) / 2.))
+        })
+    }
+
+    // Closest passable tile, searching rings around `tile`
+    pub fn nearest_passable(&self, tile: UVec2) -> Option<UVec2> {
+        let center = tile.as_ivec2();
+        (0..self.width.max(self.height) as i32).find_map(|ring| {
+            (-ring..=ring)
+                .flat_map(move |x| (-ring..=ring).map(move |y| center + IVec2::new(x, y)))
+                .filter(|cell| {
+                    (cell.x - center.x).abs() == ring || (cell.y - center.y).abs() == ring
+                })
+                .filter(|cell| {
+                    cell.x >= 0
+                        && cell.y >= 0
+                        && (cell.x as u32) < self.width
+                        && (cell.y as u32) < self.height
+                })
+                .map(|cell| cell.as_uvec2())
+                .find(|cell| self.terrain(*cell).is_passable())
+        })
+    }
+
+    // Whether a straight walk between two points stays on passable tiles
+    pub fn is_clear_line(&self, from: Vec3, to: Vec3) -> bool {
+        let samples = (from.distance(to) / (self.tile_size / 4.)).ceil().max(1.) as u32;
+        (0..=samples).all(|i| self.is_passable(from.lerp(to, i as f32 / samples as f32)))
+    }
+
+    pub fn tiles(&self) -> impl Iterator<Item = (UVec2, Terrain)> + '_ {
+        (0..self.height)
+            .flat_map(move |y| (0..self.width).map(move |x| UVec2::new(x, y)))
+            .map(|tile| (tile, self.terrain(tile)))
+    }
+
+    // Top-left tile of a random spot where a building of `footprint` fits on
+    // grass, with a passable tile right south of it for the entrance. None of
+    // its tiles is within the radius of a point in `keep_clear`, or within a
+    // tile of it.
+    pub fn find_site(&self, footprint: UVec2, keep_clear: &[(Vec3, f32)]) -> Option<UVec2> {
+        let mut rng = rand::thread_rng();
+
+        (0..500).find_map(|_| {
+            let tile = UVec2::new(
+                rng.gen_range(0..self.width.saturating_sub(footprint.x).max(1)),
+                rng.gen_range(0..self.height.saturating_sub(footprint.y).max(1)),
+            );
+            let fits = (0..footprint.x)
+                .flat_map(|x| (0..footprint.y).map(move |y| tile + UVec2::new(x, y)))
+                .all(|v| {
+                    v.x < self.width
+                        && v.y < self.height
+                        && self.terrain(v) == Terrain::Grass
+                        && keep_clear.iter().all(|(point, radius)| {
+                            self.tile_center(v).truncate().distance(point.truncate())
+                                > radius + self.tile_size
+                        })
+                });
+            let entrance = UVec2::new(tile.x + footprint.x / 2, tile.y + footprint.y);
+
+            (fits && entrance.y < self.height && self.terrain(entrance).is_passable())
+                .then_some(tile)
+        })
+    }
+
+    pub fn build(&mut self, tile: UVec2, footprint: UVec2) {
+        for x in tile.x..(tile.x + footprint.x).min(self.width) {
+            for y in tile.y..(tile.y + footprint.y).min(self.height) {
+                self.tiles[(y * self.width + x) as usize] = Terrain::Building;
+            }
+        }
+        self.passable.retain(|v| {
+            v.x < tile.x
+                || v.x >= tile.x + footprint.x
+                || v.y < tile.y
+                || v.y >= tile.y + footprint.y
+        });
+    }
+
+    // Whether one of the 4 tiles around `tile` is of that terrain
+    pub fn is_next_to(&self, tile: UVec2, terrain: Terrain) -> bool {
+        [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y]
+            .into_iter()
+            .map(|offset| tile.as_ivec2() + offset)
+            .filter(|next| {
+                next.x >= 0
+                    && next.y >= 0
+                    && (next.x as u32) < self.width
+                    && (next.y as u32) < self.height
+            })
+            .any(|next| self.terrain(next.as_uvec2()) == terrain)
+    }
+
+    pub fn random_tile(&self, accept: impl Fn(UVec2) -> bool) -> Option<UVec2> {
+        let candidates: Vec<UVec2> = self
+            .tiles()
+            .map(|(tile, _)| tile)
+            .filter(|tile| accept(*tile))
+            .collect();
+        candidates.choose(&mut rand::thread_rng()).copied()
+    }
+
+    pub fn random_passable_point(&self) -> Vec3 {
+        self.passable
+            .choose(&mut rand::thread_rng())
+            .map(|tile| self.tile_center(*tile))
+            .unwrap_or(Vec3::ZERO)
+    }
+
+    // Where a walker ends up after trying to move by `step` from `from`,
+    // slowed down by the terrain it is on. When the straight move ends on an
+    // impassable tile it slides along one axis, None when fully blocked.
+    pub fn step(&self, from: Vec3, step: Vec3) -> Option<Vec3> {
+        let Some(cost) = self.movement_cost(from) else {
+            // Let anyone standing on an impassable tile walk out of it
+            return Some(from + step);
+        };
+
+        let step = step / cost;
+        [step, Vec3::new(step.x, 0., 0.), Vec3::new(0., step.y, 0.)]
+            .into_iter()
+            .filter(|step| step.length_squared() > 0.)
+            .map(|step| from + step)
+            .find(|to| self.is_passable(*to))
+    }
+}
+
+// Planned routes between two tiles, as waypoints without the starting tile.
+// Frequent trips (home to market, to the well...) skip the search.
+#[derive(Resource, Debug, Default)]
+pub struct PathCache {
+    capacity: usize,
+    routes: HashMap<(UVec2, UVec2), Vec<Vec3>>,
+    order: VecDeque<(UVec2, UVec2)>,
+}
+
+impl PathCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ..default()
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.routes.clear();
+        self.order.clear();
+    }
+
+    pub fn get(&self, from: UVec2, to: UVec2) -> Option<&Vec<Vec3>> {
+        self.routes.get(&(from, to))
+    }
+
+    pub fn insert(&mut self, from: UVec2, to: UVec2, waypoints: Vec<Vec3>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.routes.insert((from, to), waypoints).is_none() {
+            self.order.push_back((from, to));
+        }
+
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.routes.remove(&oldest);
+            }
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+
+use crate::ecs::{
+    action::components::{Action, ActionFailure},
+    components::Walking,
+    logs::AddLogEntry,
+    map::{
+        components::Path,
+        pathfinding::plan_path,
+        resources::{PathCache, WorldMap},
+    },
+};
+
+// One flat colored sprite per tile, under the agents
+pub fn spawn_map_tiles(map: Res<WorldMap>, mut commands: Commands) {
+    for (tile, terrain) in map.tiles() {
+        commands.spawn((
+            Sprite {
+                color: terrain.color(),
+                custom_size: Some(Vec2::splat(map.tile_size)),
+                ..default()
+            },
+            Transform::from_translation(map.tile_center(tile).with_z(-10.)),
+        ));
+    }
+}
+
+// Walkers without a path, or with one planned for another destination, get
+// a new one. A removed Path (blocked on the way) is re-planned here too.
+pub fn plan_paths_system(
+    mut query: Query<(Entity, &Transform, &mut Walking, Option<&Path>)>,
+    map: Res<WorldMap>,
+    mut cache: ResMut<PathCache>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+    mut commands: Commands,
+) {
+    for (entity, transform, mut walking, path) in &mut query {
+        if walking.lifecycle().is_finished() {
+            continue;
+        }
+        if path.is_some_and(|path| path.destination == walking.destination) {
+            continue;
+        }
+
+        match plan_path(&map, &mut cache, transform.translation, walking.destination) {
+            Some(path) => {
+                walking.destination = path.destination;
+                commands.entity(entity).insert(path);
+            }
+            None => {
+                add_log_writer.send(AddLogEntry::new(entity, "Walking -> no path found"));
+                walking.lifecycle_mut().fail(ActionFailure::Blocked);
+            }
+        }
+    }
+}
+
+pub fn remove_path_with_walking(trigger: Trigger<OnRemove, Walking>, mut commands: Commands) {
+    if let Some(mut entity) = commands.get_entity(trigger.entity()) {
+        entity.remove::<Path>();
+    }
+}
@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+
+// Cheapest terrain to cross, keeps the path search heuristic admissible
+pub const MIN_MOVEMENT_COST: f32 = 0.7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Terrain {
+    #[default]
+    Grass,
+    Water,
+    Forest,
+    Road,
+    Building,
+}
+
+impl Terrain {
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Terrain::Grass),
+            '~' => Some(Terrain::Water),
+            'T' => Some(Terrain::Forest),
+            '=' => Some(Terrain::Road),
+            '#' => Some(Terrain::Building),
+            _ => None,
+        }
+    }
+
+    // How many times slower than on grass, None when it cannot be crossed
+    pub fn movement_cost(&self) -> Option<f32> {
+        match self {
+            Terrain::Grass => Some(1.),
+            Terrain::Forest => Some(2.),
+            Terrain::Road => Some(MIN_MOVEMENT_COST),
+            Terrain::Water | Terrain::Building => None,
+        }
+    }
+
+    pub fn is_passable(&self) -> bool {
+        self.movement_cost().is_some()
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            Terrain::Grass => Color::srgb(0.36, 0.55, 0.27),
+            Terrain::Water => Color::srgb(0.2, 0.4, 0.75),
+            Terrain::Forest => Color::srgb(0.13, 0.33, 0.16),
+            Terrain::Road => Color::srgb(0.6, 0.5, 0.35),
+            Terrain::Building => Color::srgb(0.45, 0.3, 0.25),
+        }
+    }
+}
@@ -1,15 +1,27 @@
+pub mod action;
 pub mod agent;
-pub mod traits;
-pub mod interaction;
-pub mod game_state;
-pub mod trade;
+pub mod building;
+pub mod buy;
 pub mod components;
-pub mod ui;
-pub mod roles;
-pub mod utils;
-pub mod logs;
-pub mod knowledge;
-pub mod talk;
 pub mod consume;
+pub mod credit;
+pub mod crowd;
+pub mod economy;
+pub mod employment;
+pub mod game_state;
+pub mod government;
+pub mod harvest;
+pub mod interaction;
+pub mod knowledge;
+pub mod logs;
+pub mod map;
+pub mod movement;
+pub mod roles;
 pub mod sell;
-pub mod buy;
+pub mod spatial;
+pub mod talk;
+pub mod task;
+pub mod trade;
+pub mod traits;
+pub mod ui;
+pub mod watchdog;
@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+
+// Walking speed of an agent on grass, in units per second. `current` is
+// `base` once slowed down by hunger, thirst and the load carried.
+#[derive(Component, Debug)]
+pub struct MovementSpeed {
+    pub base: f32,
+    pub current: f32,
+}
+
+impl MovementSpeed {
+    pub fn new(base: f32) -> Self {
+        Self {
+            base,
+            current: base,
+        }
+    }
+}
+
+// Time and distance an agent spent walking, time it did not spend on
+// anything else
+#[derive(Component, Debug, Default)]
+pub struct TravelLog {
+    pub walking_secs: f32,
+    pub distance: f32,
+    last_position: Option<Vec3>,
+}
+
+impl TravelLog {
+    pub fn record(&mut self, position: Vec3, walking: bool, delta_secs: f32) {
+        if walking {
+            self.walking_secs += delta_secs;
+            if let Some(last) = self.last_position {
+                self.distance += last.distance(position);
+            }
+        }
+        self.last_position = Some(position);
+    }
+}
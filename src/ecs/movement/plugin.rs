@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+
+use crate::ecs::{
+    game_state::GameState,
+    movement::{resources::MovementConfig, systems::*},
+};
+
+pub struct MovementPlugin;
+
+impl Plugin for MovementPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MovementConfig>()
+            .add_systems(Update, attach_movement_components)
+            .add_systems(
+                Update,
+                (movement_speed_system, travel_log_system).run_if(in_state(GameState::Running)),
+            );
+    }
+}
@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+
+#[derive(Resource, Debug, Clone)]
+pub struct MovementConfig {
+    pub base_speed: f32,
+    // spread of the base speed between agents, as a fraction of it
+    pub speed_variation: f32,
+    // walkers this close to their destination have arrived
+    pub arrival_radius: f32,
+    // partners this close to each other can interact
+    pub interaction_radius: f32,
+    // walkers blocked this many times in a row without reaching a waypoint
+    // give up
+    pub max_blocked_replans: u32,
+    // items carried (money aside) at which an agent is slowed down the most
+    pub max_load: usize,
+    pub max_load_slowdown: f32,
+    // slowdown for each of hunger and thirst
+    pub need_slowdown: f32,
+}
+
+impl Default for MovementConfig {
+    fn default() -> Self {
+        Self {
+            base_speed: 125.,
+            speed_variation: 0.2,
+            arrival_radius: 50.,
+            interaction_radius: 50.,
+            max_blocked_replans: 5,
+            max_load: 100,
+            max_load_slowdown: 0.5,
+            need_slowdown: 0.2,
+        }
+    }
+}
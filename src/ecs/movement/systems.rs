@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    core::item::ItemEnum,
+    ecs::{
+        agent::Agent,
+        components::Walking,
+        movement::{
+            components::{MovementSpeed, TravelLog},
+            resources::MovementConfig,
+        },
+    },
+};
+
+pub fn attach_movement_components(
+    query: Query<Entity, (With<Agent>, Without<MovementSpeed>)>,
+    config: Res<MovementConfig>,
+    mut commands: Commands,
+) {
+    let mut rng = rand::thread_rng();
+
+    for entity in &query {
+        let variation = rng.gen_range(-config.speed_variation..=config.speed_variation);
+        commands.entity(entity).insert((
+            MovementSpeed::new(config.base_speed * (1. + variation)),
+            TravelLog::default(),
+        ));
+    }
+}
+
+// Terrain is left out, it only matters for the tile being crossed
+pub fn movement_speed_system(
+    mut query: Query<(&Agent, &mut MovementSpeed)>,
+    config: Res<MovementConfig>,
+) {
+    for (agent, mut speed) in &mut query {
+        let load: usize = agent
+            .inventory
+            .list()
+            .into_iter()
+            .filter(|(item, _)| *item != ItemEnum::MONEY)
+            .map(|(_, qty)| qty)
+            .sum();
+        let load_factor =
+            1. - (load as f32 / config.max_load as f32).min(1.) * config.max_load_slowdown;
+
+        let needs = agent.is_hungry() as u8 + agent.is_thirsty() as u8;
+        let need_factor = (1. - needs as f32 * config.need_slowdown).max(0.);
+
+        speed.current = speed.base * load_factor * need_factor;
+    }
+}
+
+pub fn travel_log_system(
+    mut query: Query<(&Transform, &mut TravelLog, Has<Walking>)>,
+    time: Res<Time>,
+) {
+    for (transform, mut travel_log, walking) in &mut query {
+        travel_log.record(transform.translation, walking, time.delta_secs());
+    }
+}
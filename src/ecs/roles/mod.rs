@@ -1,3 +1,3 @@
 pub mod none;
-pub mod seller;
 pub mod plugin;
+pub mod seller;
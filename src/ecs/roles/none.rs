@@ -3,13 +3,13 @@ use bevy::ecs::{
     entity::Entity,
     event::EventWriter,
     query::With,
-    system::{Commands, Query},
+    system::{Commands, Query, Res},
 };
 
 use crate::ecs::{
     components::{Idle, Walking},
     logs::AddLogEntry,
-    utils::get_random_vec3,
+    map::resources::WorldMap,
 };
 
 #[derive(Component, Default)]
@@ -17,6 +17,7 @@ pub struct NoneRole;
 
 pub fn handle_idle_none_role(
     query: Query<Entity, (With<NoneRole>, With<Idle>)>,
+    map: Res<WorldMap>,
     mut commands: Commands,
     mut add_log_writer: EventWriter<AddLogEntry>,
 ) {
@@ -24,7 +25,7 @@ pub fn handle_idle_none_role(
         add_log_writer.send(AddLogEntry::new(entity, "Role -> Start Walking"));
         commands
             .entity(entity)
-            .insert(Walking::new(get_random_vec3()))
+            .insert(Walking::new(map.random_passable_point()))
             .remove::<Idle>();
     }
 }
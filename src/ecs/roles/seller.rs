@@ -1,23 +1,77 @@
 use bevy::prelude::*;
 
-use crate::ecs::{
-    components::{Idle, Walking},
-    logs::AddLogEntry,
-    sell::actions::components::Selling,
+use crate::{
+    core::item::ItemEnum,
+    ecs::{
+        agent::Agent,
+        components::{Idle, Walking},
+        harvest::{
+            actions::components::Harvesting, components::ResourceNode, resources::HarvestConfig,
+        },
+        logs::AddLogEntry,
+        movement::resources::MovementConfig,
+        sell::actions::components::Selling,
+    },
 };
 
-#[derive(Component, Default)]
+#[derive(Component)]
 pub struct SellerRole {
+    pub shop: Entity,
+    // entrance of the shop, where they stand to sell
     pub location: Vec3,
+    // what they sell, and harvest when running out of it
+    pub ware: ItemEnum,
 }
 
 pub fn handle_idle_sellers(
-    query: Query<(Entity, &Transform, &SellerRole), (With<SellerRole>, With<Idle>)>,
+    query: Query<(Entity, &Transform, &Agent, &SellerRole), With<Idle>>,
+    node_query: Query<(Entity, &ResourceNode)>,
+    config: Res<MovementConfig>,
+    harvest_config: Res<HarvestConfig>,
     mut commands: Commands,
     mut add_log_writer: EventWriter<AddLogEntry>,
 ) {
-    for (entity, &transform, seller_role) in &query {
-        if seller_role.location.distance(transform.translation) > 50. {
+    for (entity, &transform, agent, seller_role) in &query {
+        let position = transform.translation;
+
+        if agent.inventory.get_qty(seller_role.ware) < harvest_config.restock_below {
+            let closest_node = node_query
+                .iter()
+                .filter(|(_, node)| node.kind.item() == seller_role.ware && !node.is_depleted())
+                .min_by(|(_, a), (_, b)| {
+                    a.location
+                        .distance(position)
+                        .total_cmp(&b.location.distance(position))
+                });
+
+            // With every node depleted they go on selling what remains
+            if let Some((node_entity, node)) = closest_node {
+                if node.location.distance(position) > config.arrival_radius {
+                    add_log_writer.send(AddLogEntry::new(
+                        entity,
+                        format!("Role -> Low on stock, start Walking to the {:?}", node.kind)
+                            .as_str(),
+                    ));
+                    commands
+                        .entity(entity)
+                        .insert(Walking::new(node.location))
+                        .remove::<Idle>();
+                } else {
+                    add_log_writer.send(AddLogEntry::new(entity, "Start Harvesting"));
+                    commands
+                        .entity(entity)
+                        .insert(Harvesting::new(
+                            node_entity,
+                            harvest_config.amount,
+                            harvest_config.secs_per_unit,
+                        ))
+                        .remove::<Idle>();
+                }
+                continue;
+            }
+        }
+
+        if seller_role.location.distance(position) > config.arrival_radius {
             add_log_writer.send(AddLogEntry::new(
                 entity,
                 "Role -> Start Walking to sell location",
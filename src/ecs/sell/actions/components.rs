@@ -1,41 +1,28 @@
-use std::collections::HashSet;
-
 use bevy::prelude::*;
 
-use crate::ecs::{components::DurationAction, traits::*};
+use crate::ecs::action::components::*;
 
-#[derive(Component, Default)]
+#[derive(Component)]
 pub struct Selling {
-    resting_duration: f32,
-    paused: Paused
+    lifecycle: ActionLifecycle,
 }
 
 impl Selling {
     pub fn new() -> Self {
         Self {
-            resting_duration: 50.,
-            paused: Paused::default()
+            lifecycle: ActionLifecycle::with_duration(50.),
         }
     }
 }
 
-impl Pausable for Selling {
-    fn pause(&mut self, reason: PauseReason) {
-        self.paused.insert(reason);
-    }
-    fn resume(&mut self, reason: PauseReason) {
-        self.paused.remove(&reason);
-    }
-    fn is_paused(&self) -> bool {
-        self.paused.len() > 0
-    }
-}
+impl Action for Selling {
+    const KIND: ActionKind = ActionKind::Sell;
+    const PAUSE_WHILE_INTERACTING: bool = true;
 
-impl DurationAction for Selling {
-    fn get_resting_duration(&self) -> f32 {
-        self.resting_duration
+    fn lifecycle(&self) -> &ActionLifecycle {
+        &self.lifecycle
     }
-    fn progress(&mut self, time: f32) {
-        self.resting_duration -= time;
+    fn lifecycle_mut(&mut self) -> &mut ActionLifecycle {
+        &mut self.lifecycle
     }
 }
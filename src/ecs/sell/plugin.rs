@@ -1,18 +1,20 @@
 use bevy::prelude::*;
 
-use crate::{ecs::sell::actions::systems::*, GameState};
+use crate::{
+    ecs::{
+        action::plugin::RegisterAction,
+        sell::actions::{components::Selling, systems::*},
+    },
+    GameState,
+};
 
 pub struct SellPlugin;
 
 impl Plugin for SellPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
+        app.register_action::<Selling>().add_systems(
             Update,
-            (
-                handle_selling_action,
-                handle_interaction_added_while_selling,
-            ).run_if(in_state(GameState::Running)),
-        )
-        .add_observer(handle_interaction_removed_while_selling);
+            handle_selling_action.run_if(in_state(GameState::Running)),
+        );
     }
 }
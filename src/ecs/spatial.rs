@@ -0,0 +1,170 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::ecs::agent::Agent;
+
+// Uniform grid over the XY plane, rebuilt from the agents' Transform every
+// tick. Radius and nearest queries only look at the cells around the point
+// instead of every agent.
+#[derive(Resource)]
+pub struct SpatialGrid {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<(Entity, Vec2)>>,
+    // Occupied cells, so unbounded searches know when to stop
+    min_cell: IVec2,
+    max_cell: IVec2,
+}
+
+impl Default for SpatialGrid {
+    fn default() -> Self {
+        Self::new(64.)
+    }
+}
+
+impl SpatialGrid {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            min_cell: IVec2::MAX,
+            max_cell: IVec2::MIN,
+        }
+    }
+
+    fn cell_of(&self, position: Vec2) -> IVec2 {
+        (position / self.cell_size).floor().as_ivec2()
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.min_cell = IVec2::MAX;
+        self.max_cell = IVec2::MIN;
+    }
+
+    pub fn insert(&mut self, entity: Entity, position: Vec3) {
+        let position = position.truncate();
+        let cell = self.cell_of(position);
+
+        self.cells.entry(cell).or_default().push((entity, position));
+        self.min_cell = self.min_cell.min(cell);
+        self.max_cell = self.max_cell.max(cell);
+    }
+
+    // Every entity within `radius` of `center`, with its position, unordered
+    pub fn positions_within(
+        &self,
+        center: Vec3,
+        radius: f32,
+    ) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        let center = center.truncate();
+        let from = self.cell_of(center - Vec2::splat(radius));
+        let to = self.cell_of(center + Vec2::splat(radius));
+
+        (from.x..=to.x)
+            .flat_map(move |x| (from.y..=to.y).map(move |y| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .filter(move |(_, position)| position.distance(center) <= radius)
+            .copied()
+    }
+
+    // Every entity within `radius` of `center`, with its distance, unordered
+    pub fn within_radius(
+        &self,
+        center: Vec3,
+        radius: f32,
+    ) -> impl Iterator<Item = (Entity, f32)> + '_ {
+        self.positions_within(center, radius)
+            .map(move |(entity, position)| (entity, position.distance(center.truncate())))
+    }
+
+    // Up to `k` entities accepted by `filter`, closest first, no further than
+    // `max_radius`. Walks rings of cells around the center until the k-th
+    // candidate is closer than anything the next ring could hold.
+    pub fn k_nearest(
+        &self,
+        center: Vec3,
+        k: usize,
+        max_radius: f32,
+        filter: impl Fn(Entity) -> bool,
+    ) -> Vec<(Entity, f32)> {
+        let mut found: Vec<(Entity, f32)> = vec![];
+        if k == 0 || self.cells.is_empty() {
+            return found;
+        }
+
+        let center = center.truncate();
+        let origin = self.cell_of(center);
+
+        let to_bounds = (self.min_cell - origin)
+            .abs()
+            .max((self.max_cell - origin).abs())
+            .max_element();
+        let max_ring = if max_radius.is_finite() {
+            to_bounds.min((max_radius / self.cell_size).ceil() as i32 + 1)
+        } else {
+            to_bounds
+        };
+
+        for ring in 0..=max_ring {
+            for cell in ring_cells(origin, ring) {
+                let Some(entries) = self.cells.get(&cell) else {
+                    continue;
+                };
+                for (entity, position) in entries {
+                    let distance = position.distance(center);
+                    if distance <= max_radius && filter(*entity) {
+                        found.push((*entity, distance));
+                    }
+                }
+            }
+
+            found.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+            found.truncate(k);
+
+            // Cells of the next ring are at least `ring` cells away
+            if found.len() == k && found[k - 1].1 <= ring as f32 * self.cell_size {
+                break;
+            }
+        }
+
+        found
+    }
+
+    pub fn nearest(
+        &self,
+        center: Vec3,
+        max_radius: f32,
+        filter: impl Fn(Entity) -> bool,
+    ) -> Option<(Entity, f32)> {
+        self.k_nearest(center, 1, max_radius, filter).pop()
+    }
+}
+
+fn ring_cells(origin: IVec2, ring: i32) -> impl Iterator<Item = IVec2> {
+    (-ring..=ring).flat_map(move |x| {
+        (-ring..=ring)
+            .filter(move |y| x.abs() == ring || y.abs() == ring)
+            .map(move |y| origin + IVec2::new(x, y))
+    })
+}
+
+pub struct SpatialPlugin;
+
+impl Plugin for SpatialPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpatialGrid>();
+
+        // Also while paused, so agents can still be picked in the UI
+        app.add_systems(PreUpdate, update_spatial_grid_system);
+    }
+}
+
+fn update_spatial_grid_system(
+    query: Query<(Entity, &Transform), With<Agent>>,
+    mut grid: ResMut<SpatialGrid>,
+) {
+    grid.clear();
+    for (entity, transform) in &query {
+        grid.insert(entity, transform.translation);
+    }
+}
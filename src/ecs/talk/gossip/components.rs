@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+// Agents that just gossiped will not volunteer anything again for a while
+#[derive(Component, Debug)]
+pub struct GossipCooldown {
+    remaining_secs: f32,
+}
+
+impl GossipCooldown {
+    pub fn new(secs: f32) -> Self {
+        Self {
+            remaining_secs: secs,
+        }
+    }
+
+    // returns true once the cooldown is over
+    pub fn progress(&mut self, delta: f32) -> bool {
+        self.remaining_secs -= delta;
+        self.remaining_secs <= 0.
+    }
+}
@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+#[derive(Event, Debug)]
+pub struct Gossiping {
+    pub source: Entity,
+    pub target: Entity,
+}
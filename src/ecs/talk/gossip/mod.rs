@@ -0,0 +1,5 @@
+pub mod components;
+pub mod events;
+pub mod plugin;
+pub mod resources;
+mod systems;
@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+
+use crate::ecs::{
+    game_state::GameState,
+    talk::gossip::{
+        resources::{GossipConfig, KnowledgeDiffusion},
+        systems::*,
+    },
+};
+
+pub struct GossipPlugin;
+
+impl Plugin for GossipPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GossipConfig>()
+            .init_resource::<KnowledgeDiffusion>()
+            .add_systems(
+                Update,
+                (
+                    gossip_cooldown_system,
+                    spontaneous_gossip_system,
+                    knowledge_diffusion_system,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Running)),
+            )
+            .add_observer(exchange_gossip);
+    }
+}
@@ -0,0 +1,65 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::ecs::knowledge::KnowledgeId;
+
+#[derive(Resource, Debug, Clone)]
+pub struct GossipConfig {
+    // agents closer than this may start gossiping on their own
+    pub spontaneous_radius: f32,
+    // chance per second for an agent to gossip with someone close
+    pub spontaneous_chance_per_sec: f32,
+    pub cooldown_secs: f32,
+    pub diffusion_check_secs: f32,
+}
+
+impl Default for GossipConfig {
+    fn default() -> Self {
+        Self {
+            spontaneous_radius: 40.,
+            spontaneous_chance_per_sec: 0.4,
+            cooldown_secs: 15.,
+            diffusion_check_secs: 1.,
+        }
+    }
+}
+
+// Fraction of the population at which the spreading time of a fact is recorded
+pub const DIFFUSION_MILESTONES: [f32; 3] = [0.25, 0.5, 0.9];
+
+#[derive(Debug, Clone)]
+pub struct FactDiffusion {
+    // elapsed seconds when the fact was first known by someone
+    pub first_seen_secs: f32,
+    pub known_by: usize,
+    // made up or misremembered, never matched the ground truth
+    pub false_fact: bool,
+    // seconds since first seen to reach each of DIFFUSION_MILESTONES
+    pub milestones: [Option<f32>; DIFFUSION_MILESTONES.len()],
+}
+
+impl FactDiffusion {
+    pub fn new(first_seen_secs: f32) -> Self {
+        Self {
+            first_seen_secs,
+            known_by: 0,
+            false_fact: false,
+            milestones: [None; DIFFUSION_MILESTONES.len()],
+        }
+    }
+}
+
+// How fast facts (a new seller, ...) become known across the population
+#[derive(Resource, Debug, Default)]
+pub struct KnowledgeDiffusion {
+    pub population: usize,
+    pub facts: HashMap<KnowledgeId, FactDiffusion>,
+}
+
+impl KnowledgeDiffusion {
+    pub fn coverage(&self, id: &KnowledgeId) -> f32 {
+        match (self.facts.get(id), self.population) {
+            (Some(fact), population) if population > 0 => fact.known_by as f32 / population as f32,
+            _ => 0.,
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use rand::{random, seq::SliceRandom};
+
+use crate::ecs::{
+    agent::Agent,
+    components::Interacting,
+    interaction::group::components::GroupMember,
+    knowledge::{AgentKnowledge, KnowledgeConfig, KnowledgeId, SharedKnowledge},
+    logs::AddLogEntry,
+    spatial::SpatialGrid,
+    talk::gossip::{
+        components::GossipCooldown,
+        events::Gossiping,
+        resources::{FactDiffusion, GossipConfig, KnowledgeDiffusion, DIFFUSION_MILESTONES},
+    },
+};
+
+pub fn gossip_cooldown_system(
+    mut query: Query<(Entity, &mut GossipCooldown)>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (entity, mut cooldown) in &mut query {
+        if cooldown.progress(time.delta_secs()) {
+            commands.entity(entity).remove::<GossipCooldown>();
+        }
+    }
+}
+
+// Agents passing close to each other, not in the middle of a conversation,
+// may start gossiping on their own
+pub fn spontaneous_gossip_system(
+    agent_query: Query<(Entity, &Transform, Has<GossipCooldown>), With<AgentKnowledge>>,
+    busy_query: Query<(Has<Interacting>, Has<GroupMember>)>,
+    grid: Res<SpatialGrid>,
+    config: Res<GossipConfig>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    let chance = config.spontaneous_chance_per_sec * time.delta_secs();
+
+    let candidates: HashMap<Entity, Vec3> = agent_query
+        .iter()
+        .filter(|(entity, _, cooling_down)| {
+            !cooling_down && busy_query.get(*entity) == Ok((false, false))
+        })
+        .map(|(entity, transform, _)| (entity, transform.translation))
+        .collect();
+
+    let mut paired: HashSet<Entity> = HashSet::new();
+
+    for (entity, position) in &candidates {
+        if paired.contains(entity) || random::<f32>() > chance {
+            continue;
+        }
+
+        let partner = grid
+            .nearest(*position, config.spontaneous_radius, |other| {
+                other != *entity && !paired.contains(&other) && candidates.contains_key(&other)
+            })
+            .map(|(other, _)| other);
+
+        let Some(partner) = partner else {
+            continue;
+        };
+
+        paired.insert(*entity);
+        paired.insert(partner);
+
+        for agent in [*entity, partner] {
+            commands
+                .entity(agent)
+                .insert(GossipCooldown::new(config.cooldown_secs));
+        }
+
+        commands.trigger(Gossiping {
+            source: *entity,
+            target: partner,
+        });
+    }
+}
+
+// Each one tells the other up to `bandwidth` facts the other does not know
+// yet, of any kind
+pub fn exchange_gossip(
+    trigger: Trigger<Gossiping>,
+    mut agent_query: Query<(&Name, &mut AgentKnowledge)>,
+    mut shared_knowledge: ResMut<SharedKnowledge>,
+    config: Res<KnowledgeConfig>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+) {
+    let Ok([(name, mut knowledge), (partner_name, mut partner_knowledge)]) =
+        agent_query.get_many_mut([trigger.source, trigger.target])
+    else {
+        return;
+    };
+
+    let mut rng = rand::thread_rng();
+
+    let mut told = knowledge.unknown_to(&partner_knowledge);
+    told.shuffle(&mut rng);
+    told.truncate(config.bandwidth);
+
+    let mut heard = partner_knowledge.unknown_to(&knowledge);
+    heard.shuffle(&mut rng);
+    heard.truncate(config.bandwidth);
+
+    for id in &told {
+        partner_knowledge.hear(
+            *id,
+            trigger.source,
+            knowledge.confidence_of(id),
+            &mut shared_knowledge,
+            &config,
+        );
+    }
+    for id in &heard {
+        knowledge.hear(
+            *id,
+            trigger.target,
+            partner_knowledge.confidence_of(id),
+            &mut shared_knowledge,
+            &config,
+        );
+    }
+
+    add_log_writer.send(AddLogEntry::new(
+        trigger.source,
+        format!(
+            "Gossip with {} -> told {} facts, heard {}",
+            partner_name,
+            told.len(),
+            heard.len()
+        )
+        .as_str(),
+    ));
+    add_log_writer.send(AddLogEntry::new(
+        trigger.target,
+        format!(
+            "Gossip with {} -> told {} facts, heard {}",
+            name,
+            heard.len(),
+            told.len()
+        )
+        .as_str(),
+    ));
+}
+
+// Counts how many agents know each fact and records how long it took to
+// reach the milestones of DIFFUSION_MILESTONES since the fact appeared.
+pub fn knowledge_diffusion_system(
+    agent_query: Query<&AgentKnowledge, With<Agent>>,
+    shared_knowledge: Res<SharedKnowledge>,
+    config: Res<GossipConfig>,
+    time: Res<Time>,
+    mut diffusion: ResMut<KnowledgeDiffusion>,
+    mut since_last_check: Local<f32>,
+) {
+    *since_last_check += time.delta_secs();
+    if *since_last_check < config.diffusion_check_secs {
+        return;
+    }
+    *since_last_check = 0.;
+
+    let now = time.elapsed_secs();
+
+    for id in shared_knowledge.get_all() {
+        diffusion
+            .facts
+            .entry(id)
+            .or_insert_with(|| FactDiffusion::new(now));
+    }
+
+    let mut known_by: HashMap<KnowledgeId, usize> = HashMap::new();
+    let mut population = 0;
+    for knowledge in &agent_query {
+        population += 1;
+        for id in knowledge.known() {
+            *known_by.entry(*id).or_default() += 1;
+        }
+    }
+
+    diffusion.population = population;
+
+    for (id, fact) in diffusion.facts.iter_mut() {
+        fact.known_by = known_by.get(id).cloned().unwrap_or_default();
+        fact.false_fact = shared_knowledge.is_false(id);
+
+        for (milestone, reached) in DIFFUSION_MILESTONES.iter().zip(fact.milestones.iter_mut()) {
+            if reached.is_none()
+                && population > 0
+                && fact.known_by as f32 / population as f32 >= *milestone
+            {
+                *reached = Some(now - fact.first_seen_secs);
+                info!(
+                    "Knowledge {} known by {}% of the agents after {:.1}s",
+                    id,
+                    milestone * 100.,
+                    now - fact.first_seen_secs
+                );
+            }
+        }
+    }
+}
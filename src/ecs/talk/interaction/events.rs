@@ -6,21 +6,18 @@ use crate::ecs::{components::InteractionId, knowledge::KnowledgeId};
 pub struct StartTalkEvent {
     pub target: Entity,
     pub source: Entity,
-    pub interaction_id: InteractionId
-}
-
-#[derive(Event, Debug)]
-pub struct SendKnowledgeEvent {
     pub interaction_id: InteractionId,
-    pub target: Entity,
-    pub source: Entity,
-    pub knowledge_id: KnowledgeId
+    // the target is a seller that will recommend itself instead of answering
+    pub lying: bool,
 }
 
 #[derive(Event, Debug)]
-pub struct ShareKnowledgeFinalizedEvent {
+pub struct SendKnowledgeEvent {
     pub interaction_id: InteractionId,
     pub target: Entity,
     pub source: Entity,
-    pub success: bool,
+    // facts told, with how sure the teller is about each one
+    pub knowledge: Vec<(KnowledgeId, f32)>,
+    // whether any seller the source asked about is among the shared facts
+    pub answered: bool,
 }
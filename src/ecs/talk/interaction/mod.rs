@@ -1,4 +1,4 @@
+pub mod components;
 mod events;
-mod systems;
 pub mod plugin;
-pub mod components;
+mod systems;
@@ -1,30 +1,19 @@
-use bevy::app::{App, Plugin, Update};
+use bevy::app::{App, Plugin};
 
-use crate::ecs::talk::interaction::events::{
-    SendKnowledgeEvent, ShareKnowledgeFinalizedEvent, StartTalkEvent,
-};
 use crate::ecs::talk::interaction::systems::{
-    handle_interaction_timed_out, handle_knowlegde_share_requested_system,
-    handle_knowlegde_share_started_system, handle_knowlegde_shared_system,
-    share_knowledge_finalized_system,
+    handle_interaction_ended, handle_knowlegde_share_requested, handle_knowlegde_share_started,
+    handle_knowlegde_shared, handle_lying_seller_share_started, share_knowledge_in_gossip_circle,
 };
 
 pub struct TalkInteractionPlugin;
 
 impl Plugin for TalkInteractionPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<SendKnowledgeEvent>()
-            .add_event::<ShareKnowledgeFinalizedEvent>()
-            .add_event::<StartTalkEvent>()
-            .add_observer(handle_interaction_timed_out)
-            .add_systems(
-                Update,
-                (
-                    handle_knowlegde_share_requested_system,
-                    handle_knowlegde_share_started_system,
-                    handle_knowlegde_shared_system,
-                    share_knowledge_finalized_system,
-                ),
-            );
+        app.add_observer(handle_knowlegde_share_requested)
+            .add_observer(handle_knowlegde_share_started)
+            .add_observer(handle_lying_seller_share_started)
+            .add_observer(handle_knowlegde_shared)
+            .add_observer(handle_interaction_ended)
+            .add_observer(share_knowledge_in_gossip_circle);
     }
 }
@@ -1,231 +1,376 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
 
 use crate::ecs::{
     components::Interacting,
-    interaction::common::events::InteractionTimedOut,
-    knowledge::AgentKnowledge,
+    interaction::{
+        common::{
+            components::{InteractionEndReason, RejectionReason},
+            events::{InteractionEnded, InteractionReady},
+        },
+        group::{
+            components::{GroupInteraction, GroupInteractionKind},
+            events::{GroupInteractionActive, LeaveGroupInteraction},
+        },
+    },
+    knowledge::{
+        AgentKnowledge, Honesty, KnowledgeConfig, KnowledgeFact, KnowledgeId, SharedKnowledge,
+    },
     logs::AddLogEntry,
+    roles::seller::SellerRole,
     talk::{
         events::*,
         interaction::{
             components::KnowledgeSharingInteraction,
-            events::{SendKnowledgeEvent, ShareKnowledgeFinalizedEvent, StartTalkEvent},
+            events::{SendKnowledgeEvent, StartTalkEvent},
         },
     },
 };
 
-pub fn handle_knowlegde_share_requested_system(
-    mut query: Query<(Entity, &KnowledgeSharingInteraction, &mut Interacting)>,
-    mut start_talk_writer: EventWriter<StartTalkEvent>,
+pub fn handle_knowlegde_share_requested(
+    trigger: Trigger<InteractionReady>,
+    query: Query<(&KnowledgeSharingInteraction, &Interacting)>,
+    liar_query: Query<&Honesty, With<SellerRole>>,
     mut add_log_writer: EventWriter<AddLogEntry>,
+    mut commands: Commands,
 ) {
-    for (entity, knowledge_sharing, mut entity_interacting) in &mut query {
-        if entity == knowledge_sharing.target && entity_interacting.is_ready() {
-            add_log_writer.send(AddLogEntry::new(
-                entity,
-                format!(
-                    "KnowledgeSharingInteraction {} -> Sending StartTalkEvent",
-                    entity_interacting.id
-                )
-                .as_str(),
-            ));
-            start_talk_writer.send(StartTalkEvent {
-                target: knowledge_sharing.target,
-                source: knowledge_sharing.source,
-                interaction_id: entity_interacting.id,
-            });
+    let entity = trigger.target;
 
-            entity_interacting.set_started();
+    if let Ok((knowledge_sharing, entity_interacting)) = query.get(entity) {
+        if entity != knowledge_sharing.target
+            || knowledge_sharing.source != trigger.source
+            || entity_interacting.id != trigger.id
+        {
+            return;
         }
+
+        add_log_writer.send(AddLogEntry::new(
+            entity,
+            format!(
+                "KnowledgeSharingInteraction {} -> Sending StartTalkEvent",
+                entity_interacting.id
+            )
+            .as_str(),
+        ));
+        commands.trigger(StartTalkEvent {
+            target: knowledge_sharing.target,
+            source: knowledge_sharing.source,
+            interaction_id: entity_interacting.id,
+            lying: liar_query.get(entity).is_ok_and(|honesty| honesty.lies()),
+        });
     }
 }
 
-pub fn handle_knowlegde_share_started_system(
+pub fn handle_knowlegde_share_started(
+    trigger: Trigger<StartTalkEvent>,
     target_query: Query<(&KnowledgeSharingInteraction, &AgentKnowledge, &Interacting)>,
     source_query: Query<
         (Entity, &AgentKnowledge),
         (With<KnowledgeSharingInteraction>, With<Interacting>),
     >,
-    mut send_knowledge_writer: EventWriter<SendKnowledgeEvent>,
-    mut share_knowledge_finalized_writer: EventWriter<ShareKnowledgeFinalizedEvent>,
-    mut start_talk_reader: EventReader<StartTalkEvent>,
+    shared_knowledge: Res<SharedKnowledge>,
+    config: Res<KnowledgeConfig>,
     mut add_log_writer: EventWriter<AddLogEntry>,
+    mut commands: Commands,
 ) {
-    for event in start_talk_reader.read() {
-        if let Ok((knowledge_sharing, target_agent_knowledge, interacting)) =
-            &target_query.get(event.target)
-        {
-            add_log_writer.send(AddLogEntry::new(
-                event.target,
-                format!("Start talking. ID: {}", interacting.id).as_str(),
-            ));
-
-            if let Ok((source_entity, source_agent_knowledge)) = source_query.get(event.source) {
-                let target_known_sellers =
-                    target_agent_knowledge.get_sellers_of(&knowledge_sharing.seller_of);
-                let source_known_sellers: Vec<Entity> = source_agent_knowledge
-                    .get_sellers_of(&knowledge_sharing.seller_of)
-                    .iter()
-                    .map(|(seller, _)| seller.clone())
-                    .collect();
-
-                let mut nothing_to_share = true;
-
-                for (seller, knowledge_id) in target_known_sellers {
-                    if !source_known_sellers.contains(&seller) {
-                        send_knowledge_writer.send(SendKnowledgeEvent {
-                            interaction_id: interacting.id,
-                            source: source_entity,
-                            target: event.target,
-                            knowledge_id,
-                        });
-
-                        nothing_to_share = false;
-
-                        break; // only ONE will be shared
-                    }
-                }
+    let event = trigger.event();
+
+    if event.lying {
+        return;
+    }
+
+    if let Ok((knowledge_sharing, target_agent_knowledge, interacting)) =
+        &target_query.get(event.target)
+    {
+        // the target moved on to another interaction since
+        if interacting.id != event.interaction_id {
+            return;
+        }
+
+        add_log_writer.send(AddLogEntry::new(
+            event.target,
+            format!("Start talking. ID: {}", interacting.id).as_str(),
+        ));
 
-                if nothing_to_share {
-                    // share_knowledge_finalized_writer.send(ShareKnowledgeFinalizedEvent {
-                    //     interaction_id: interacting.id,
-                    //     target: event.target,
-                    //     success: false,
-                    //     should_trigger_feedback: true,
-                    // });
-                    share_knowledge_finalized_writer.send(ShareKnowledgeFinalizedEvent {
-                        interaction_id: interacting.id,
-                        target: event.target,
-                        source: source_entity,
-                        success: false,
-                    });
+        if let Ok((source_entity, source_agent_knowledge)) = source_query.get(event.source) {
+            // sellers the source asked about come first, then anything else
+            // the target knows and the source does not, up to the bandwidth
+            let mut knowledge_ids: Vec<KnowledgeId> = target_agent_knowledge
+                .get_sellers_of(&knowledge_sharing.seller_of, &shared_knowledge)
+                .map(|(_, id)| id)
+                .filter(|id| !source_agent_knowledge.knows(id))
+                .take(config.bandwidth)
+                .collect();
+
+            let answered = !knowledge_ids.is_empty();
+
+            for id in target_agent_knowledge.unknown_to(source_agent_knowledge) {
+                if knowledge_ids.len() >= config.bandwidth {
+                    break;
                 }
+                if !knowledge_ids.contains(&id) {
+                    knowledge_ids.push(id);
+                }
+            }
+
+            if knowledge_ids.is_empty() {
+                commands.trigger(InteractionEnded {
+                    id: interacting.id,
+                    source: source_entity,
+                    target: event.target,
+                    reason: InteractionEndReason::Rejected(RejectionReason::Declined),
+                });
             } else {
-                // TODO: finish interaction
-                println!("No source found: {}", interacting.id);
+                commands.trigger(SendKnowledgeEvent {
+                    interaction_id: interacting.id,
+                    source: source_entity,
+                    target: event.target,
+                    knowledge: knowledge_ids
+                        .into_iter()
+                        .map(|id| (id, target_agent_knowledge.confidence_of(&id)))
+                        .collect(),
+                    answered,
+                });
             }
+        } else {
+            commands.trigger(InteractionEnded {
+                id: interacting.id,
+                source: event.source,
+                target: event.target,
+                reason: InteractionEndReason::PartnerGone,
+            });
         }
     }
 }
 
-pub fn handle_knowlegde_shared_system(
-    mut source_query: Query<
-        (
-            &KnowledgeSharingInteraction,
-            &mut AgentKnowledge,
-            &Interacting,
-        ),
-        (With<KnowledgeSharingInteraction>, With<Interacting>),
-    >,
-    mut send_knowledge_reader: EventReader<SendKnowledgeEvent>,
-    mut share_knowledge_finalized_writer: EventWriter<ShareKnowledgeFinalizedEvent>,
+// A seller recommending itself, whatever it really sells, keeping quiet
+// about the others
+pub fn handle_lying_seller_share_started(
+    trigger: Trigger<StartTalkEvent>,
+    target_query: Query<(&KnowledgeSharingInteraction, &SellerRole, &Interacting)>,
+    source_query: Query<(), (With<KnowledgeSharingInteraction>, With<Interacting>)>,
+    mut shared_knowledge: ResMut<SharedKnowledge>,
     mut add_log_writer: EventWriter<AddLogEntry>,
+    mut commands: Commands,
 ) {
-    for event in send_knowledge_reader.read() {
-        if let Ok((_, source_agent_knowledge, interacting)) =
-            &mut source_query.get_mut(event.source)
-        {
-            add_log_writer.send(AddLogEntry::new(
-                event.source,
-                format!(
-                    "Received requested knowledge. Interaction ID {}",
-                    interacting.id
-                )
-                .as_str(),
-            ));
-
-            source_agent_knowledge.add(event.knowledge_id);
-
-            // share_knowledge_finalized_writer.send(ShareKnowledgeFinalizedEvent {
-            //     interaction_id: interacting.id,
-            //     target: event.source,
-            //     source: event.source,
-            //     success: true,
-            //     should_trigger_feedback: true,
-            // });
-        }
+    let event = trigger.event();
 
-        share_knowledge_finalized_writer.send(ShareKnowledgeFinalizedEvent {
-            interaction_id: event.interaction_id,
-            target: event.target,
+    if !event.lying {
+        return;
+    }
+
+    let Ok((knowledge_sharing, seller_role, interacting)) = target_query.get(event.target) else {
+        return;
+    };
+    if interacting.id != event.interaction_id {
+        return;
+    }
+
+    if !source_query.contains(event.source) {
+        commands.trigger(InteractionEnded {
+            id: interacting.id,
             source: event.source,
-            success: true,
+            target: event.target,
+            reason: InteractionEndReason::PartnerGone,
         });
+        return;
     }
+
+    let advert = shared_knowledge.add_false_fact(KnowledgeFact::SellerInfo {
+        entity: event.target,
+        shop: seller_role.shop,
+        wares: vec![knowledge_sharing.seller_of],
+    });
+
+    add_log_writer.send(AddLogEntry::new(
+        event.target,
+        format!(
+            "Lying to {}: recommending myself as seller of {:?}",
+            knowledge_sharing.source_name, knowledge_sharing.seller_of
+        )
+        .as_str(),
+    ));
+
+    commands.trigger(SendKnowledgeEvent {
+        interaction_id: interacting.id,
+        source: event.source,
+        target: event.target,
+        knowledge: vec![(advert, 1.)],
+        answered: true,
+    });
 }
 
-pub fn share_knowledge_finalized_system(
-    mut share_knowledge_finalized_reader: EventReader<ShareKnowledgeFinalizedEvent>,
+pub fn handle_knowlegde_shared(
+    trigger: Trigger<SendKnowledgeEvent>,
+    mut source_query: Query<
+        (&mut AgentKnowledge, &Interacting),
+        (With<KnowledgeSharingInteraction>, With<Interacting>),
+    >,
+    mut shared_knowledge: ResMut<SharedKnowledge>,
+    knowledge_config: Res<KnowledgeConfig>,
     mut add_log_writer: EventWriter<AddLogEntry>,
     mut commands: Commands,
 ) {
-    for event in share_knowledge_finalized_reader.read() {
+    let event = trigger.event();
+
+    if let Ok((mut source_agent_knowledge, interacting)) = source_query.get_mut(event.source) {
         add_log_writer.send(AddLogEntry::new(
-            event.target,
+            event.source,
             format!(
-                "Finishing Interaction {} - knowledge sharing with {}",
-                event.interaction_id,
-                if event.success { "SUCCESS" } else { "FAILURE" }
+                "Received {} facts ({}). Interaction ID {}",
+                event.knowledge.len(),
+                if event.answered {
+                    "answered"
+                } else {
+                    "not what was asked"
+                },
+                interacting.id
             )
             .as_str(),
         ));
 
+        for (id, confidence) in &event.knowledge {
+            source_agent_knowledge.hear(
+                *id,
+                event.target,
+                *confidence,
+                &mut shared_knowledge,
+                &knowledge_config,
+            );
+        }
+    }
+
+    commands.trigger(InteractionEnded {
+        id: event.interaction_id,
+        source: event.source,
+        target: event.target,
+        reason: if event.answered {
+            InteractionEndReason::Completed
+        } else {
+            InteractionEndReason::Rejected(RejectionReason::Declined)
+        },
+    });
+}
+
+pub fn handle_interaction_ended(
+    trigger: Trigger<InteractionEnded>,
+    agent_query: Query<(Entity, &Interacting), With<KnowledgeSharingInteraction>>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+    mut commands: Commands,
+) {
+    let success = trigger.reason == InteractionEndReason::Completed;
+
+    for entity in [trigger.source, trigger.target] {
+        let Ok((entity, interacting)) = agent_query.get(entity) else {
+            continue;
+        };
+
+        if trigger.id != interacting.id {
+            continue;
+        }
+
         add_log_writer.send(AddLogEntry::new(
-            event.source,
+            entity,
             format!(
-                "Finishing Interaction {} - knowledge sharing with {}",
-                event.interaction_id,
-                if event.success { "SUCCESS" } else { "FAILURE" }
+                "Finishing Interaction {} - knowledge sharing with {} ({:?})",
+                interacting.id,
+                if success { "SUCCESS" } else { "FAILURE" },
+                trigger.reason
             )
             .as_str(),
         ));
 
         commands
-            .entity(event.target)
+            .entity(entity)
             .remove::<(Interacting, KnowledgeSharingInteraction)>();
 
-        commands
-            .entity(event.source)
-            .remove::<(Interacting, KnowledgeSharingInteraction)>();
+        if entity != trigger.source {
+            continue;
+        }
 
-        if event.success {
+        if success {
             commands.trigger(TalkFinishedWithSuccess {
-                source: event.source,
+                source: trigger.source,
             });
         } else {
             commands.trigger(TalkFinishedWithFailure {
-                target: event.target,
-                source: event.source,
-                interaction_id: event.interaction_id,
+                target: trigger.target,
+                source: trigger.source,
+                interaction_id: interacting.id,
             });
         }
     }
 }
 
-pub fn handle_interaction_timed_out(
-    trigger: Trigger<InteractionTimedOut>,
-    agent_query: Query<(Entity, &Interacting, &KnowledgeSharingInteraction)>,
+// Every participant of the circle learns every seller of the topic known by
+// the others. Those who learned nothing leave the circle empty handed.
+pub fn share_knowledge_in_gossip_circle(
+    trigger: Trigger<GroupInteractionActive>,
+    mut group_query: Query<&mut GroupInteraction>,
+    mut agent_query: Query<&mut AgentKnowledge>,
+    mut shared_knowledge: ResMut<SharedKnowledge>,
+    config: Res<KnowledgeConfig>,
     mut add_log_writer: EventWriter<AddLogEntry>,
     mut commands: Commands,
 ) {
-    for (entity, interacting, _) in &agent_query {
-        if trigger.id == interacting.id {
-            add_log_writer.send(AddLogEntry::new(
-                entity,
-                format!(
-                    "KnowledgeSharingInteraction -> Interaction {} timed out",
-                    interacting.id
-                )
-                .as_str(),
-            ));
-            commands
-                .entity(entity)
-                .remove::<(Interacting, KnowledgeSharingInteraction)>()
-                .trigger(TalkFinishedWithFailure {
-                    target: interacting.target,
-                    source: interacting.source,
-                    interaction_id: interacting.id,
-                });
+    let Ok(mut group) = group_query.get_mut(trigger.group) else {
+        return;
+    };
+
+    let GroupInteractionKind::GossipCircle { topic } = group.kind else {
+        return;
+    };
+
+    // each seller told by the one most sure about it
+    let mut pooled: HashMap<KnowledgeId, (f32, Entity)> = HashMap::new();
+    for agent in group.participants() {
+        if let Ok(knowledge) = agent_query.get(*agent) {
+            for (_, id) in knowledge.get_sellers_of(&topic, &shared_knowledge) {
+                let confidence = knowledge.confidence_of(&id);
+                let told = pooled.entry(id).or_insert((confidence, *agent));
+                if confidence > told.0 {
+                    *told = (confidence, *agent);
+                }
+            }
         }
     }
+
+    // closed here, so the first ones leaving don't end it for the others
+    group.set_finished();
+
+    for agent in group.participants() {
+        let mut learned = 0;
+
+        if let Ok(mut knowledge) = agent_query.get_mut(*agent) {
+            let unknown: Vec<(KnowledgeId, (f32, Entity))> = pooled
+                .iter()
+                .filter(|(id, _)| !knowledge.knows(id))
+                .map(|(id, told)| (*id, *told))
+                .take(config.bandwidth)
+                .collect();
+
+            for (id, (confidence, teller)) in unknown {
+                knowledge.hear(id, teller, confidence, &mut shared_knowledge, &config);
+                learned += 1;
+            }
+        }
+
+        add_log_writer.send(AddLogEntry::new(
+            *agent,
+            format!(
+                "Gossip circle {} -> learned {} sellers of {:?}",
+                group.id, learned, topic
+            )
+            .as_str(),
+        ));
+
+        commands.trigger(LeaveGroupInteraction {
+            group: trigger.group,
+            agent: *agent,
+            reason: if learned > 0 {
+                InteractionEndReason::Completed
+            } else {
+                InteractionEndReason::Rejected(RejectionReason::Declined)
+            },
+        });
+    }
 }
@@ -1,4 +1,5 @@
+pub mod events;
+pub mod gossip;
 pub mod interaction;
 pub mod plugin;
 pub mod task;
-pub mod events;
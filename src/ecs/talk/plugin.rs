@@ -3,9 +3,11 @@ use bevy::prelude::*;
 use crate::{
     ecs::talk::{
         events::{TalkFinishedWithFailure, TalkFinishedWithSuccess},
+        gossip::plugin::GossipPlugin,
         interaction::plugin::TalkInteractionPlugin,
-        task::systems::*,
+        task::{components::TalkTask, systems::*},
     },
+    ecs::task::plugin::RegisterTask,
     GameState,
 };
 
@@ -13,19 +15,21 @@ pub struct TalkPlugin;
 
 impl Plugin for TalkPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(TalkInteractionPlugin)
+        app.add_plugins((TalkInteractionPlugin, GossipPlugin))
+            .register_task::<TalkTask>()
             .add_event::<TalkFinishedWithSuccess>()
             .add_event::<TalkFinishedWithFailure>()
             .add_systems(
                 Update,
                 (
-                    handle_added_talk_task,
+                    (gather_gossip_circles, handle_added_talk_task).chain(),
                     handle_get_close_to_target_while_talk_task,
                 )
                     .run_if(in_state(GameState::Running)),
             )
             .add_observer(handle_talk_failure)
             .add_observer(handle_talk_success)
-            .add_observer(handle_waiting_interaction_timed_out);
+            .add_observer(handle_left_gossip_circle)
+            .add_observer(handle_waiting_interaction_ended);
     }
 }
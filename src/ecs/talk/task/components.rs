@@ -1,12 +1,23 @@
 use bevy::{prelude::*, utils::HashSet};
 
-use crate::{core::item::ItemEnum, ecs::components::InteractionId};
+use crate::{
+    core::item::ItemEnum,
+    ecs::{
+        components::InteractionId,
+        task::components::{Task, TaskKind},
+    },
+};
 
 #[derive(Component, Debug)]
 pub struct TalkTask {
     pub seller_of: ItemEnum,
     pub tried: HashSet<Entity>,
     pub current_interaction: Option<(InteractionId, Entity, Name)>,
+    // gossip circle (group interaction entity) it is taking part in
+    pub current_group: Option<Entity>,
+    // seconds left before looking again for someone to ask
+    pub retry_in: f32,
+    pub retries: u32,
 }
 
 impl TalkTask {
@@ -15,6 +26,13 @@ impl TalkTask {
             seller_of,
             tried: HashSet::new(),
             current_interaction: None,
+            current_group: None,
+            retry_in: 0.,
+            retries: 0,
         }
     }
 }
+
+impl Task for TalkTask {
+    const KIND: TaskKind = TaskKind::Talk;
+}
@@ -1,34 +1,150 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashSet};
 
+use crate::core::item::ItemEnum;
 use crate::ecs::agent::Agent;
 use crate::ecs::components::*;
 use crate::ecs::interaction::common::components::{
-    AgentInteractionItem, AgentInteractionKind, AgentInteractionQueue,
+    AgentInteractionItem, AgentInteractionKind, AgentInteractionQueue, InteractionEndReason,
 };
-use crate::ecs::interaction::common::events::WaitingInteractionTimedOut;
+use crate::ecs::interaction::common::events::{InteractionEnded, InteractionRequested};
+use crate::ecs::interaction::common::resources::InteractionConfig;
+use crate::ecs::interaction::group::components::{
+    GroupInteraction, GroupInteractionKind, GroupMember,
+};
+use crate::ecs::interaction::group::events::{JoinGroupInteraction, LeftGroupInteraction};
 use crate::ecs::logs::*;
+use crate::ecs::movement::resources::MovementConfig;
+use crate::ecs::spatial::SpatialGrid;
 use crate::ecs::talk::events::*;
 use crate::ecs::talk::interaction::components::KnowledgeSharingInteraction;
 use crate::ecs::talk::task::components::TalkTask;
+use crate::ecs::task::{commands::TaskCommandsExt, components::TaskFailure};
+
+// Agents looking for sellers of the same item, close to each other, ask
+// together: they join an open gossip circle nearby or open a new one with
+// the other searching agents around. The others keep asking one by one.
+pub fn gather_gossip_circles(
+    mut talk_query: Query<(Entity, &Transform, &mut TalkTask), Without<GroupMember>>,
+    interacting_query: Query<(), With<Interacting>>,
+    group_query: Query<(Entity, &GroupInteraction)>,
+    config: Res<InteractionConfig>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+    mut commands: Commands,
+) {
+    let searching: Vec<(Entity, Vec3, ItemEnum)> = talk_query
+        .iter()
+        .filter(|(entity, _, task)| {
+            task.current_interaction.is_none()
+                && task.current_group.is_none()
+                && !interacting_query.contains(*entity)
+        })
+        .map(|(entity, transform, task)| (entity, transform.translation, task.seller_of))
+        .collect();
+
+    let mut handled: HashSet<Entity> = HashSet::new();
+
+    for (entity, position, topic) in &searching {
+        if !handled.insert(*entity) {
+            continue;
+        }
+
+        let Ok((_, _, mut talk_task)) = talk_query.get_mut(*entity) else {
+            continue;
+        };
+
+        let open_circle = group_query.iter().find(|(_, group)| {
+            group.kind == GroupInteractionKind::GossipCircle { topic: *topic }
+                && group.is_gathering()
+                && !group.is_full()
+                && !talk_task.tried.contains(&group.host)
+                && group.center.distance(*position) <= config.group_join_radius
+        });
+
+        if let Some((group_entity, group)) = open_circle {
+            add_log_writer.send(AddLogEntry::new(
+                *entity,
+                format!("TalkTask -> joining gossip circle {}", group.id).as_str(),
+            ));
+
+            talk_task.current_group = Some(group_entity);
+            commands.trigger(JoinGroupInteraction {
+                group: group_entity,
+                agent: *entity,
+            });
+            continue;
+        }
+
+        let neighbours: Vec<Entity> = searching
+            .iter()
+            .filter(|(other, other_position, other_topic)| {
+                !handled.contains(other)
+                    && other_topic == topic
+                    && other_position.distance(*position) <= config.group_join_radius
+            })
+            .map(|(other, _, _)| *other)
+            .take(config.max_group_participants - 1)
+            .collect();
+
+        if neighbours.is_empty() {
+            continue;
+        }
+
+        let group = GroupInteraction::new(
+            GroupInteractionKind::GossipCircle { topic: *topic },
+            *entity,
+            *position,
+            config.max_group_participants,
+            config.group_gathering_secs,
+        );
+
+        add_log_writer.send(AddLogEntry::new(
+            *entity,
+            format!(
+                "TalkTask -> opening gossip circle {} with {} agents",
+                group.id,
+                neighbours.len()
+            )
+            .as_str(),
+        ));
+
+        let group_entity = commands.spawn(group).id();
+
+        for agent in std::iter::once(*entity).chain(neighbours) {
+            handled.insert(agent);
+
+            if let Ok((_, _, mut task)) = talk_query.get_mut(agent) {
+                task.current_group = Some(group_entity);
+            }
+
+            commands.trigger(JoinGroupInteraction {
+                group: group_entity,
+                agent,
+            });
+        }
+    }
+}
 
 pub fn handle_added_talk_task(
     mut source_agent_query: Query<(Entity, &Transform, &Name, &mut TalkTask), Without<Interacting>>,
-    mut target_agent_query: Query<(Entity, &Transform, &Name, &mut AgentInteractionQueue)>, // maybe without<Interaction>
+    target_agent_query: Query<(Entity, &Transform, &Name), With<AgentInteractionQueue>>, // maybe without<Interaction>
+    grid: Res<SpatialGrid>,
+    config: Res<InteractionConfig>,
+    time: Res<Time>,
     mut add_log_writer: EventWriter<AddLogEntry>,
     mut commands: Commands,
 ) {
     for (source_entity, source_transform, source_name, mut talk_task) in &mut source_agent_query {
-        if talk_task.current_interaction.is_some() {
+        if talk_task.current_interaction.is_some() || talk_task.current_group.is_some() {
             continue;
         }
 
         if talk_task.tried.len() > 3 {
-            // although using TalkFinishedWithSuccess here,
-            // this is a failure
-            commands.trigger(TalkFinishedWithSuccess {
-                source: source_entity,
-            });
+            commands.fail_task::<TalkTask>(source_entity, TaskFailure::TooManyAttempts);
+            continue;
+        }
 
+        if talk_task.retry_in > 0. {
+            talk_task.retry_in -= time.delta_secs();
             continue;
         }
 
@@ -37,34 +153,15 @@ pub fn handle_added_talk_task(
             format!("TalkTask -> searching an Agent to ask").as_str(),
         ));
 
-        let mut best: Option<(Entity, f32)> = None;
-        for (entity, target_transform, _, _) in &target_agent_query {
-            if entity.eq(&source_entity) {
-                continue;
-            }
-            if talk_task.tried.contains(&entity) {
-                continue;
-            }
-
-            let d2 = source_transform
-                .translation
-                .distance_squared(target_transform.translation);
-
-            // TODO: set a maximum acceptable distance
-            match best {
-                None => best = Some((entity, d2)),
-                Some((_, best_d2)) => {
-                    if d2 < best_d2 {
-                        best = Some((entity, d2))
-                    }
-                }
-            }
-        }
+        // TODO: set a maximum acceptable distance
+        let best = grid.nearest(source_transform.translation, f32::INFINITY, |entity| {
+            entity != source_entity
+                && !talk_task.tried.contains(&entity)
+                && target_agent_query.contains(entity)
+        });
 
         if let Some((closest_entity, _)) = best {
-            if let Ok((_, _, name, mut agent_interation_queue)) =
-                target_agent_query.get_mut(closest_entity)
-            {
+            if let Ok((_, _, name)) = target_agent_query.get(closest_entity) {
                 let waiting =
                     WaitingInteraction::new_with_duration(source_entity, closest_entity, 10.);
                 let interaction_id = waiting.id;
@@ -78,31 +175,38 @@ pub fn handle_added_talk_task(
                     .as_str(),
                 ));
 
-                agent_interation_queue.add(AgentInteractionItem {
-                    id: interaction_id,
-                    kind: AgentInteractionKind::Ask(KnowledgeSharingInteraction::new(
-                        talk_task.seller_of,
-                        source_entity,       // source
-                        closest_entity,      // target
-                        source_name.clone(), // source name
-                        name.clone(),        // target name
-                    )),
-                });
-
                 talk_task.current_interaction =
                     Some((interaction_id, closest_entity, name.clone()));
 
                 commands.entity(source_entity).insert(waiting);
+                commands.trigger(InteractionRequested {
+                    source: source_entity,
+                    target: closest_entity,
+                    item: AgentInteractionItem {
+                        id: interaction_id,
+                        kind: AgentInteractionKind::Ask(KnowledgeSharingInteraction::new(
+                            talk_task.seller_of,
+                            source_entity,       // source
+                            closest_entity,      // target
+                            source_name.clone(), // source name
+                            name.clone(),        // target name
+                        )),
+                    },
+                });
             }
+        } else if talk_task.retries < config.max_ask_retries {
+            // failing right away would start the BuyTask again, which would
+            // come back here in the very same frame
+            talk_task.retries += 1;
+            talk_task.retry_in = config.ask_retry_secs;
         } else {
-            // TODO
-            info!("no agents found");
+            commands.fail_task::<TalkTask>(source_entity, TaskFailure::NoAgentsToAsk);
         }
     }
 }
 
-pub fn handle_waiting_interaction_timed_out(
-    trigger: Trigger<WaitingInteractionTimedOut>,
+pub fn handle_waiting_interaction_ended(
+    trigger: Trigger<InteractionEnded>,
     agent_query: Query<(&WaitingInteraction, &TalkTask)>,
     mut add_log_writer: EventWriter<AddLogEntry>,
     mut commands: Commands,
@@ -112,8 +216,8 @@ pub fn handle_waiting_interaction_timed_out(
             add_log_writer.send(AddLogEntry::new(
                 trigger.source,
                 format!(
-                    "TalkTask -> WaitingInteraction {} timed out",
-                    waiting_interaction.id
+                    "TalkTask -> WaitingInteraction {} ended ({:?})",
+                    waiting_interaction.id, trigger.reason
                 )
                 .as_str(),
             ));
@@ -136,6 +240,7 @@ pub fn handle_get_close_to_target_while_talk_task(
         Without<Walking>,
     >,
     target_agent_query: Query<(Entity, &Transform), With<Agent>>,
+    config: Res<MovementConfig>,
     mut add_log_writer: EventWriter<AddLogEntry>,
     mut commands: Commands,
 ) {
@@ -149,11 +254,11 @@ pub fn handle_get_close_to_target_while_talk_task(
                 }
             }
 
-            if let Ok((_, target_transform)) = target_agent_query.get(target_entity.clone()) {
+            if let Ok((_, target_transform)) = target_agent_query.get(*target_entity) {
                 if source_transform
                     .translation
                     .distance(target_transform.translation)
-                    > 50.
+                    > config.interaction_radius
                 {
                     add_log_writer.send(AddLogEntry::new(
                         source_entity,
@@ -163,9 +268,10 @@ pub fn handle_get_close_to_target_while_talk_task(
                         )
                         .as_str(),
                     ));
-                    commands
-                        .entity(source_entity)
-                        .insert(Walking::new_without_idle(target_transform.translation));
+                    commands.entity(source_entity).insert(
+                        Walking::new_without_idle(target_transform.translation)
+                            .arriving_within(config.interaction_radius),
+                    );
                 }
             }
         }
@@ -175,15 +281,36 @@ pub fn handle_get_close_to_target_while_talk_task(
 pub fn handle_talk_success(
     trigger: Trigger<TalkFinishedWithSuccess>,
     agent_query: Query<&TalkTask>,
-    mut add_log_writer: EventWriter<AddLogEntry>,
     mut commands: Commands,
 ) {
     if let Ok(_) = agent_query.get(trigger.source) {
-        add_log_writer.send(AddLogEntry::new(trigger.source, "TalkTask -> Back to Idle"));
-        commands
-            .entity(trigger.source)
-            .insert(Idle)
-            .remove::<TalkTask>();
+        commands.succeed_task::<TalkTask>(trigger.source);
+    }
+}
+
+pub fn handle_left_gossip_circle(
+    trigger: Trigger<LeftGroupInteraction>,
+    agent_query: Query<&TalkTask>,
+    mut commands: Commands,
+) {
+    let Ok(task) = agent_query.get(trigger.agent) else {
+        return;
+    };
+
+    if task.current_group != Some(trigger.group) {
+        return;
+    }
+
+    if trigger.reason == InteractionEndReason::Completed {
+        commands.trigger(TalkFinishedWithSuccess {
+            source: trigger.agent,
+        });
+    } else {
+        commands.trigger(TalkFinishedWithFailure {
+            target: trigger.host,
+            source: trigger.agent,
+            interaction_id: trigger.id,
+        });
     }
 }
 
@@ -204,6 +331,17 @@ pub fn handle_talk_failure(
             ));
 
             task.tried.insert(partner);
+        } else if task.current_group.take().is_some() {
+            add_log_writer.send(AddLogEntry::new(
+                trigger.source,
+                format!(
+                    "TalkTask -> gossip circle {} failed. Will try with another Agent",
+                    trigger.interaction_id
+                )
+                .as_str(),
+            ));
+
+            task.tried.insert(trigger.target);
         }
     }
 }
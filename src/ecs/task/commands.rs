@@ -0,0 +1,105 @@
+use std::marker::PhantomData;
+
+use bevy::{ecs::world::Command, prelude::*};
+
+use crate::ecs::{
+    components::Idle,
+    task::{
+        components::{Task, TaskFailure},
+        events::{TaskFailed, TaskStarted, TaskSucceeded},
+        resources::TaskRegistry,
+    },
+};
+
+pub struct StartTask<T: Task> {
+    pub target: Entity,
+    pub task: T,
+}
+
+impl<T: Task> Command for StartTask<T> {
+    fn apply(self, world: &mut World) {
+        let registry = world.resource::<TaskRegistry>().clone();
+
+        let Ok(mut entity) = world.get_entity_mut(self.target) else {
+            return;
+        };
+
+        // only one task at a time: the new task replaces any other one and Idle
+        for id in registry.ids() {
+            if entity.contains_id(id) {
+                entity.remove_by_id(id);
+            }
+        }
+        entity.remove::<Idle>().insert(self.task);
+
+        world.trigger(TaskStarted {
+            target: self.target,
+            kind: T::KIND,
+        });
+    }
+}
+
+pub struct FinishTask<T: Task> {
+    pub target: Entity,
+    pub failure: Option<TaskFailure>,
+    marker: PhantomData<T>,
+}
+
+impl<T: Task> Command for FinishTask<T> {
+    fn apply(self, world: &mut World) {
+        let Ok(entity) = world.get_entity(self.target) else {
+            return;
+        };
+
+        // already finished (or replaced) earlier in this frame
+        if !entity.contains::<T>() {
+            return;
+        }
+
+        // lifecycle events are triggered before removing the task,
+        // so observers can still read it
+        match self.failure {
+            None => world.trigger(TaskSucceeded {
+                target: self.target,
+                kind: T::KIND,
+            }),
+            Some(reason) => world.trigger(TaskFailed {
+                target: self.target,
+                kind: T::KIND,
+                reason,
+            }),
+        }
+
+        if let Ok(mut entity) = world.get_entity_mut(self.target) {
+            entity.remove::<T>().insert(Idle);
+        }
+    }
+}
+
+pub trait TaskCommandsExt {
+    fn start_task<T: Task>(&mut self, target: Entity, task: T);
+    fn succeed_task<T: Task>(&mut self, target: Entity);
+    fn fail_task<T: Task>(&mut self, target: Entity, reason: TaskFailure);
+}
+
+impl TaskCommandsExt for Commands<'_, '_> {
+    fn start_task<T: Task>(&mut self, target: Entity, task: T) {
+        self.queue(StartTask { target, task });
+    }
+
+    fn succeed_task<T: Task>(&mut self, target: Entity) {
+        self.queue(FinishTask::<T> {
+            target,
+            failure: None,
+            marker: PhantomData,
+        });
+    }
+
+    fn fail_task<T: Task>(&mut self, target: Entity, reason: TaskFailure) {
+        self.queue(FinishTask::<T> {
+            target,
+            failure: Some(reason),
+            marker: PhantomData,
+        });
+    }
+}
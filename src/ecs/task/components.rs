@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaskKind {
+    Buy,
+    Consume,
+    Talk,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskFailure {
+    NoKnownSellers,
+    AllSellersTried,
+    NoAgentsToAsk,
+    TooManyAttempts,
+    Stuck,
+}
+
+/// Every task component (BuyTask, ConsumeTask, TalkTask...) implements this.
+/// Tasks must be started and finished through `TaskCommandsExt`, which keeps
+/// exactly one task or `Idle` on the agent.
+pub trait Task: Component {
+    const KIND: TaskKind;
+}
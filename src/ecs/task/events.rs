@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+
+use crate::ecs::task::components::{TaskFailure, TaskKind};
+
+#[derive(Event, Debug)]
+pub struct TaskStarted {
+    pub target: Entity,
+    pub kind: TaskKind,
+}
+
+#[derive(Event, Debug)]
+pub struct TaskSucceeded {
+    pub target: Entity,
+    pub kind: TaskKind,
+}
+
+#[derive(Event, Debug)]
+pub struct TaskFailed {
+    pub target: Entity,
+    pub kind: TaskKind,
+    pub reason: TaskFailure,
+}
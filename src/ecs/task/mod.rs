@@ -0,0 +1,6 @@
+pub mod commands;
+pub mod components;
+pub mod events;
+pub mod plugin;
+pub mod resources;
+mod systems;
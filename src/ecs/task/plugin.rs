@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+
+use crate::ecs::{
+    game_state::GameState,
+    task::{components::Task, events::*, resources::TaskRegistry, systems::*},
+};
+
+pub struct TaskPlugin;
+
+impl Plugin for TaskPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TaskRegistry>()
+            .add_event::<TaskStarted>()
+            .add_event::<TaskSucceeded>()
+            .add_event::<TaskFailed>()
+            .add_observer(log_task_started)
+            .add_observer(log_task_succeeded)
+            .add_observer(log_task_failed)
+            .add_systems(
+                Last,
+                task_watchdog_system.run_if(in_state(GameState::Running)),
+            );
+    }
+}
+
+pub trait RegisterTask {
+    fn register_task<T: Task>(&mut self) -> &mut Self;
+}
+
+impl RegisterTask for App {
+    fn register_task<T: Task>(&mut self) -> &mut Self {
+        let component_id = self.world_mut().register_component::<T>();
+        self.world_mut()
+            .get_resource_or_insert_with(TaskRegistry::default)
+            .register(T::KIND, component_id);
+        self
+    }
+}
@@ -0,0 +1,31 @@
+use bevy::{
+    ecs::{component::ComponentId, system::Resource, world::EntityRef},
+    utils::HashMap,
+};
+
+use crate::ecs::task::components::TaskKind;
+
+// Filled by `register_task`, so starting a task can remove any other task
+// and the watchdog can check every agent without knowing every task component
+#[derive(Resource, Default, Debug, Clone)]
+pub struct TaskRegistry {
+    tasks: HashMap<TaskKind, ComponentId>,
+}
+
+impl TaskRegistry {
+    pub fn register(&mut self, kind: TaskKind, id: ComponentId) {
+        self.tasks.insert(kind, id);
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.tasks.values().copied()
+    }
+
+    pub fn find_on(&self, entity: &EntityRef) -> Vec<TaskKind> {
+        self.tasks
+            .iter()
+            .filter(|(_, id)| entity.contains_id(**id))
+            .map(|(kind, _)| *kind)
+            .collect()
+    }
+}
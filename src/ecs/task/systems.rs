@@ -0,0 +1,83 @@
+use bevy::{prelude::*, utils::HashSet};
+
+use crate::ecs::{
+    action::resources::ActionRegistry,
+    agent::Agent,
+    components::Idle,
+    logs::AddLogEntry,
+    task::{events::*, resources::TaskRegistry},
+};
+
+// Agents must always have exactly one task or Idle, unless an action is
+// keeping them busy (e.g. NoneRole walking around or a seller Selling).
+// Reports every agent breaking that rule once, until it recovers.
+pub fn task_watchdog_system(
+    query: Query<EntityRef, With<Agent>>,
+    task_registry: Res<TaskRegistry>,
+    action_registry: Res<ActionRegistry>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+    mut reported: Local<HashSet<Entity>>,
+) {
+    for agent in &query {
+        let entity = agent.id();
+        let tasks = task_registry.find_on(&agent);
+        let actions = action_registry.find_on(&agent);
+        let idle = agent.contains::<Idle>();
+
+        let problem = if tasks.is_empty() && actions.is_empty() && !idle {
+            Some("no task, no action and no Idle".to_string())
+        } else if tasks.len() > 1 {
+            Some(format!("more than one task {:?}", tasks))
+        } else if !tasks.is_empty() && idle {
+            Some(format!("task {:?} while Idle", tasks))
+        } else {
+            None
+        };
+
+        match problem {
+            Some(description) => {
+                if reported.insert(entity) {
+                    warn!("Watchdog -> agent {} has {}", entity, description);
+                    add_log_writer.send(AddLogEntry::new(
+                        entity,
+                        format!("Watchdog -> {}", description).as_str(),
+                    ));
+                }
+            }
+            None => {
+                reported.remove(&entity);
+            }
+        }
+    }
+}
+
+pub fn log_task_started(
+    trigger: Trigger<TaskStarted>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+) {
+    add_log_writer.send(AddLogEntry::new(
+        trigger.target,
+        format!("Start {:?}Task", trigger.kind).as_str(),
+    ));
+}
+
+pub fn log_task_succeeded(
+    trigger: Trigger<TaskSucceeded>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+) {
+    add_log_writer.send(AddLogEntry::new(
+        trigger.target,
+        format!("{:?}Task finished with success. Back to Idle", trigger.kind).as_str(),
+    ));
+}
+
+pub fn log_task_failed(trigger: Trigger<TaskFailed>, mut add_log_writer: EventWriter<AddLogEntry>) {
+    add_log_writer.send(AddLogEntry::new(
+        trigger.target,
+        format!(
+            "{:?}Task finished with FAILURE ({:?})",
+            trigger.kind, trigger.reason
+        )
+        .as_str(),
+    ));
+}
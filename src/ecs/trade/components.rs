@@ -35,12 +35,11 @@ pub struct TradeInteraction {
 impl TradeInteraction {
     pub fn new(
         trade: TradeNegotiation,
+        interaction: Entity,
         interaction_id: InteractionId,
-        source: Entity,
-        target: Entity,
     ) -> Self {
         Self {
-            interacting: Interacting::new_with_id(interaction_id, source, target),
+            interacting: Interacting::new(interaction, interaction_id),
             trade,
         }
     }
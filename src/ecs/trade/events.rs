@@ -1,7 +1,10 @@
 use bevy::prelude::*;
 
+use crate::ecs::components::InteractionId;
+
 #[derive(Event, Debug)]
 pub struct OfferMade {
+    pub id: InteractionId,
     pub target: Entity,
     pub quantity: usize,
     pub price: usize,
@@ -12,10 +15,12 @@ pub struct OfferAgreed {
     pub target: Entity,
     pub quantity: usize,
     pub price: usize,
+    // part of the price the buyer owes the seller
+    pub credit: usize,
 }
 
 #[derive(Event, Debug)]
 pub struct TradeFinalized {
     pub target: Entity,
-    pub success: bool
+    pub success: bool,
 }
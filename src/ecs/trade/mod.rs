@@ -1,4 +1,5 @@
-mod events;
-mod systems;
 pub mod components;
+pub mod events;
 pub mod plugin;
+pub mod resources;
+pub mod systems;
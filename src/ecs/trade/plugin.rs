@@ -1,27 +1,28 @@
 use bevy::prelude::*;
 
-use crate::ecs::trade::events::*;
-use crate::ecs::trade::systems::*;
-use crate::GameState;
+use crate::ecs::{
+    buy::tasks::systems::handle_buy_task,
+    game_state::GameState,
+    trade::{resources::TradeConfig, systems::*},
+};
 
 pub struct TradePlugin;
 
 impl Plugin for TradePlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<OfferMade>()
-            .add_event::<OfferAgreed>()
-            .add_event::<TradeFinalized>()
-            .add_observer(handle_interaction_timed_out)
+        app.init_resource::<TradeConfig>()
             .add_systems(
                 Update,
-                (
-                    seller_makes_offer_system,
-                    buyer_evaluates_offer_system,
-                    handle_offer_agreed_system,
-                    handle_trade_finalized,
-                )
+                (open_auctions_system, join_auctions_system)
                     .chain()
+                    .before(handle_buy_task)
                     .run_if(in_state(GameState::Running)),
-            );
+            )
+            .add_observer(seller_makes_offer)
+            .add_observer(buyer_evaluates_offer)
+            .add_observer(handle_offer_agreed)
+            .add_observer(handle_trade_finalized)
+            .add_observer(handle_interaction_ended)
+            .add_observer(run_auction);
     }
 }
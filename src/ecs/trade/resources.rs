@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+#[derive(Resource, Debug, Clone)]
+pub struct TradeConfig {
+    pub base_unit_price: f32,
+    // how much the price goes up, relative to the base one, when the seller
+    // is about to run out
+    pub scarcity_markup: f32,
+    // stock under which the seller starts raising prices
+    pub comfortable_stock: usize,
+    // stock at which a seller with enough buyers lined up auctions it
+    pub auction_below: usize,
+    pub min_bidders: usize,
+    // bidders never bid more than this times the usual price
+    pub auction_max_markup: f32,
+}
+
+impl Default for TradeConfig {
+    fn default() -> Self {
+        Self {
+            base_unit_price: 3.,
+            scarcity_markup: 2.,
+            comfortable_stock: 20,
+            auction_below: 3,
+            min_bidders: 2,
+            auction_max_markup: 2.,
+        }
+    }
+}
+
+impl TradeConfig {
+    // Sellers low on stock (their resource nodes running dry) ask for more
+    pub fn unit_price(&self, stock: usize) -> usize {
+        let shortage = 1. - (stock as f32 / self.comfortable_stock.max(1) as f32).min(1.);
+        (self.base_unit_price * (1. + self.scarcity_markup * shortage)).round() as usize
+    }
+}
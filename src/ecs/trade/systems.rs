@@ -1,203 +1,619 @@
+use std::cmp::Reverse;
+
 use bevy::prelude::*;
 
 use crate::{
     core::item::ItemEnum,
     ecs::{
+        action::components::{Action, ActionFailure},
         agent::Agent,
         buy::{actions::components::Buying, tasks::components::BuyTask},
-        components::{Idle, Interacting},
-        interaction::common::events::InteractionTimedOut,
+        components::{Interacting, InteractionId, WaitingInteraction},
+        credit::events::{CreditExtended, CreditRequested},
+        crowd::components::SellerQueue,
+        government::{
+            events::{TaxCollected, TaxKind},
+            resources::GovernmentConfig,
+        },
+        interaction::{
+            common::{
+                components::{InteractionEndReason, RejectionReason},
+                events::{InteractionEnded, InteractionReady},
+                resources::InteractionConfig,
+            },
+            group::{
+                components::{GroupInteraction, GroupInteractionKind, GroupMember},
+                events::{
+                    EndGroupInteraction, GroupInteractionActive, JoinGroupInteraction,
+                    LeaveGroupInteraction,
+                },
+            },
+        },
+        knowledge::{AgentKnowledge, KnowledgeConfig, KnowledgeFact, SharedKnowledge},
         logs::AddLogEntry,
+        roles::seller::SellerRole,
         sell::actions::components::Selling,
+        task::commands::TaskCommandsExt,
         trade::{
             components::{TradeInteraction, TradeNegotiation, TradeRole},
             events::{OfferAgreed, OfferMade, TradeFinalized},
+            resources::TradeConfig,
         },
     },
 };
 
-pub fn seller_makes_offer_system(
-    mut seller_query: Query<
-        (Entity, &Agent, &mut TradeNegotiation, &Interacting),
-        // TODO: this can lead to problems since the Buyer won't be immediatelly ready
-        // to answer the seller offer through OfferMade event
-        With<Selling>,
-    >,
-    mut offer_made_writer: EventWriter<OfferMade>,
-    mut trade_finalized_writer: EventWriter<TradeFinalized>,
+// Both sides are ready: the seller makes the offer right away and the
+// whole protocol (offer, agreement, settlement) runs through observers
+pub fn seller_makes_offer(
+    trigger: Trigger<InteractionReady>,
+    mut seller_query: Query<(&Agent, &mut TradeNegotiation), With<Interacting>>,
+    selling_query: Query<(), With<Selling>>,
+    config: Res<TradeConfig>,
+    mut commands: Commands,
 ) {
-    for (seller_entity, agent, mut trade, interacting) in &mut seller_query {
-        if interacting.is_waiting() {
-            continue;
-        }
+    let seller_entity = trigger.target;
+
+    let Ok((agent, mut trade)) = seller_query.get_mut(seller_entity) else {
+        return;
+    };
+
+    if trade.role != TradeRole::Seller {
+        return;
+    }
+
+    let seller_amount = agent.inventory.get_qty(trade.item);
+
+    if seller_amount == 0 || !selling_query.contains(seller_entity) {
+        commands.trigger(InteractionEnded {
+            id: trigger.id,
+            source: trigger.source,
+            target: seller_entity,
+            reason: InteractionEndReason::Rejected(RejectionReason::NotSelling),
+        });
+        return;
+    }
+
+    if seller_amount < trade.quantity {
+        trade.quantity = seller_amount;
+    }
+    let price = trade.quantity * config.unit_price(seller_amount);
+    trade.price = Some(price);
+
+    commands.trigger(OfferMade {
+        id: trigger.id,
+        target: trade.partner,
+        quantity: trade.quantity,
+        price,
+    });
+}
+
+// What a buyer weighs an offer with
+type NegotiatingBuyer<'a> = (&'a Agent, &'a TradeNegotiation);
+
+pub fn buyer_evaluates_offer(
+    trigger: Trigger<OfferMade>,
+    buyer_query: Query<NegotiatingBuyer, (With<Buying>, With<Interacting>)>,
+    seller_query: Query<&Agent, With<Selling>>,
+    mut knowledge_query: Query<&mut AgentKnowledge>,
+    mut shared_knowledge: ResMut<SharedKnowledge>,
+    knowledge_config: Res<KnowledgeConfig>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
 
-        let seller_amount = agent.inventory.get_qty(trade.item);
+    if let Ok((agent, trade)) = buyer_query.get(event.target) {
+        if let Ok(mut knowledge) = knowledge_query.get_mut(event.target) {
+            knowledge.witness(
+                KnowledgeFact::PriceInfo {
+                    seller: trade.partner,
+                    item: trade.item,
+                    unit_price: event.price as f32 / event.quantity.max(1) as f32,
+                },
+                &mut shared_knowledge,
+                &knowledge_config,
+            );
+        }
 
-        if seller_amount == 0 {
-            trade_finalized_writer.send(TradeFinalized {
+        // another buyer may have emptied the stall since the offer
+        let stock = seller_query
+            .get(trade.partner)
+            .map_or(0, |seller| seller.inventory.get_qty(trade.item));
+        if stock < event.quantity {
+            commands.trigger(InteractionEnded {
+                id: event.id,
+                source: event.target,
                 target: trade.partner,
-                success: false,
+                reason: InteractionEndReason::Rejected(RejectionReason::NotSelling),
             });
-            trade_finalized_writer.send(TradeFinalized {
-                target: seller_entity,
-                success: false,
-            });
-            continue;
+            return;
         }
 
-        if seller_amount < trade.quantity {
-            trade.quantity = seller_amount;
+        let money = agent.inventory.get_qty(ItemEnum::MONEY);
+        if money >= event.price {
+            accept_offer(
+                &mut commands,
+                event.id,
+                event.target,
+                trade.partner,
+                event.quantity,
+                event.price,
+                0,
+            );
+        } else {
+            // short of money, the seller may let it owe the rest
+            commands.trigger(CreditRequested {
+                id: event.id,
+                buyer: event.target,
+                seller: trade.partner,
+                quantity: event.quantity,
+                price: event.price,
+                cash: money,
+            });
         }
-        let price = trade.quantity * 3;
-        trade.price = Some(price);
+    } else {
+        warn!("No target agent found for event: {:?}", event);
+    }
+}
 
-        offer_made_writer.send(OfferMade {
-            target: trade.partner,
-            quantity: trade.quantity,
+// Settles the trade on both sides and ends the interaction
+pub fn accept_offer(
+    commands: &mut Commands,
+    id: InteractionId,
+    buyer: Entity,
+    seller: Entity,
+    quantity: usize,
+    price: usize,
+    credit: usize,
+) {
+    for target in [seller, buyer] {
+        commands.trigger(OfferAgreed {
+            target,
             price,
+            quantity,
+            credit,
         });
     }
+
+    commands.trigger(InteractionEnded {
+        id,
+        source: buyer,
+        target: seller,
+        reason: InteractionEndReason::Completed,
+    });
 }
 
-pub fn buyer_evaluates_offer_system(
-    mut buyer_query: Query<
-        (Entity, &Agent, &TradeNegotiation),
-        (With<Buying>, With<TradeNegotiation>, With<Interacting>),
-    >,
-    mut offer_agreed_writer: EventWriter<OfferAgreed>,
-    mut offer_made_reader: EventReader<OfferMade>,
-    mut trade_finalized_writer: EventWriter<TradeFinalized>,
+pub fn handle_offer_agreed(
+    trigger: Trigger<OfferAgreed>,
+    mut target_query: Query<(&mut Agent, &TradeNegotiation), With<Interacting>>,
+    config: Res<GovernmentConfig>,
+    mut commands: Commands,
 ) {
-    for event in offer_made_reader.read() {
-        if let Ok((entity, agent, trade)) = buyer_query.get_mut(event.target) {
-            if agent.inventory.get_qty(ItemEnum::MONEY) >= event.price {
-                // trade.quantity = event.quantity;
-                // trade.price = Some(event.price);
-
-                offer_agreed_writer.send(OfferAgreed {
-                    target: trade.partner,
-                    price: event.price,
-                    quantity: event.quantity,
-                });
-
-                offer_agreed_writer.send(OfferAgreed {
-                    target: entity,
-                    price: event.price,
-                    quantity: event.quantity,
-                });
-            } else {
-                // TODO: improve (decrease qty, make a new offer...)
-                trade_finalized_writer.send(TradeFinalized {
-                    target: trade.partner,
-                    success: false,
-                });
+    let event = trigger.event();
 
-                trade_finalized_writer.send(TradeFinalized {
-                    target: entity,
-                    success: false,
+    if let Ok((mut agent, trade)) = target_query.get_mut(event.target) {
+        // the price is for the whole quantity
+        let quantity = event.quantity;
+        let paid = event.price - event.credit;
+        if trade.role == TradeRole::Buyer {
+            if let Err(err) = agent.inventory.remove(ItemEnum::MONEY, paid) {
+                warn!("{} can't pay for the trade: {:?}", event.target, err);
+                return;
+            }
+            agent.inventory.add(trade.item, quantity);
+            if event.credit > 0 {
+                commands.trigger(CreditExtended {
+                    creditor: trade.partner,
+                    debtor: event.target,
+                    principal: event.credit,
                 });
             }
         } else {
-            println!("No target agent found for event: {:?}", event);
+            if let Err(err) = agent.inventory.remove(trade.item, quantity) {
+                warn!("{} sold out before the trade: {:?}", event.target, err);
+                return;
+            }
+            let earned = paid;
+            let tax = config.tax_on(TaxKind::Sales, earned);
+            agent.inventory.add(ItemEnum::MONEY, earned - tax);
+            if tax > 0 {
+                commands.trigger(TaxCollected {
+                    payer: event.target,
+                    kind: TaxKind::Sales,
+                    amount: tax,
+                });
+            }
         }
+    } else {
+        warn!("No target agent found for event: {:?}", event);
     }
 }
 
-pub fn handle_offer_agreed_system(
-    mut target_query: Query<(&mut Agent, &TradeNegotiation), With<Interacting>>,
-    mut offer_agreed_reader: EventReader<OfferAgreed>,
-    mut trade_finalized_writer: EventWriter<TradeFinalized>,
+pub fn handle_trade_finalized(
+    trigger: Trigger<TradeFinalized>,
+    mut target_query: Query<(&TradeNegotiation, Option<&mut Buying>, &Interacting)>,
+    mut commands: Commands,
+    mut add_log_writer: EventWriter<AddLogEntry>,
 ) {
-    for event in offer_agreed_reader.read() {
-        if let Ok((mut agent, trade)) = target_query.get_mut(event.target) {
-            let price = event.price;
-            let quantity = event.quantity;
-            if trade.role == TradeRole::Buyer {
-                agent.inventory.remove(ItemEnum::MONEY, price * quantity);
-                agent.inventory.add(trade.item, quantity);
-            } else {
-                agent.inventory.add(ItemEnum::MONEY, price * quantity);
-                agent.inventory.remove(trade.item, quantity);
+    let event = trigger.event();
+
+    let Ok((trade, buying, interacting)) = target_query.get_mut(event.target) else {
+        warn!("No target agent found for event: {:?}", event);
+        return;
+    };
+
+    if trade.role == TradeRole::Buyer {
+        if event.success {
+            add_log_writer.send(AddLogEntry::new(
+                event.target,
+                format!(
+                    "Trade with {} finished with success. Interaction ID: {}",
+                    trade.partner, interacting.id
+                )
+                .as_str(),
+            ));
+            if let Some(mut buying) = buying {
+                buying.lifecycle_mut().complete();
             }
 
-            trade_finalized_writer.send(TradeFinalized {
-                target: event.target,
-                success: true,
-            });
+            commands.entity(event.target).remove::<TradeInteraction>();
+            commands.succeed_task::<BuyTask>(event.target);
         } else {
-            println!("No target agent found for event: {:?}", event);
+            add_log_writer.send(AddLogEntry::new(
+                event.target,
+                format!(
+                    "Trade with {} finished with FAILURE. Interaction ID: {}",
+                    trade.partner, interacting.id
+                )
+                .as_str(),
+            ));
+
+            // BuyTask learns about the tried seller through ActionFailed
+            if let Some(mut buying) = buying {
+                buying
+                    .lifecycle_mut()
+                    .fail(ActionFailure::InteractionFailed);
+            }
+
+            commands.entity(event.target).remove::<TradeInteraction>();
         }
+    } else {
+        let description = format!(
+            "Trade with {} finished. Interaction ID: {}",
+            trade.partner, interacting.id
+        );
+        add_log_writer.send(AddLogEntry::new(event.target, description.as_str()));
+        commands.entity(event.target).remove::<TradeInteraction>();
     }
 }
 
-pub fn handle_trade_finalized(
-    mut target_query: Query<(&TradeNegotiation, Option<&mut BuyTask>, &Interacting)>,
-    mut trade_finalized_reader: EventReader<TradeFinalized>,
+pub fn handle_interaction_ended(
+    trigger: Trigger<InteractionEnded>,
+    agent_query: Query<(Entity, &Interacting), With<TradeNegotiation>>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
     mut commands: Commands,
+) {
+    for entity in [trigger.source, trigger.target] {
+        let Ok((entity, interacting)) = agent_query.get(entity) else {
+            continue;
+        };
+
+        if trigger.id != interacting.id {
+            continue;
+        }
+
+        if trigger.reason != InteractionEndReason::Completed {
+            add_log_writer.send(AddLogEntry::new(
+                entity,
+                format!("Trade -> Interaction ended: {:?}", trigger.reason).as_str(),
+            ));
+        }
+
+        commands.trigger(TradeFinalized {
+            target: entity,
+            success: trigger.reason == InteractionEndReason::Completed,
+        });
+    }
+}
+
+// Buyers lined up at the seller, looking for its item and not already asking
+// it for a trade
+fn free_bidders(
+    queue: &SellerQueue,
+    item: ItemEnum,
+    buyer_query: &Query<(&BuyTask, Has<Buying>, Has<WaitingInteraction>)>,
+    busy_query: &Query<(Has<Interacting>, Has<GroupMember>)>,
+) -> Vec<Entity> {
+    queue
+        .buyers()
+        .map(|(_, buyer)| buyer)
+        .filter(|buyer| {
+            buyer_query
+                .get(*buyer)
+                .is_ok_and(|(task, buying, waiting)| task.item == item && !buying && !waiting)
+                && busy_query
+                    .get(*buyer)
+                    .is_ok_and(|(interacting, member)| !interacting && !member)
+        })
+        .collect()
+}
+
+// A seller about to run out, with buyers lined up, auctions its last units
+// instead of serving the queue first come first served
+pub fn open_auctions_system(
+    seller_query: Query<(Entity, &Agent, &SellerRole, &SellerQueue), With<Selling>>,
+    buyer_query: Query<(&BuyTask, Has<Buying>, Has<WaitingInteraction>)>,
+    busy_query: Query<(Has<Interacting>, Has<GroupMember>)>,
+    trade_config: Res<TradeConfig>,
+    interaction_config: Res<InteractionConfig>,
     mut add_log_writer: EventWriter<AddLogEntry>,
+    mut commands: Commands,
 ) {
-    for event in trade_finalized_reader.read() {
-        if let Ok((trade, buy_task, interacting)) = target_query.get_mut(event.target) {
-            if trade.role == TradeRole::Buyer {
-                if event.success {
-                    add_log_writer.send(AddLogEntry::new(
-                        event.target,
-                        format!(
-                            "Trade with {} finished with success. Interaction ID: {}",
-                            trade.partner, interacting.id
-                        )
-                        .as_str(),
-                    ));
-                    commands
-                        .entity(event.target)
-                        .insert(Idle)
-                        .remove::<(TradeInteraction, Buying, BuyTask)>();
-                } else {
-                    add_log_writer.send(AddLogEntry::new(
-                        event.target,
-                        format!(
-                            "Trade with {} finished with FAILURE. Interaction ID: {}",
-                            trade.partner, interacting.id
-                        )
-                        .as_str(),
-                    ));
-
-                    if let Some(mut task) = buy_task {
-                        task.add_tried(trade.partner);
-                    }
-
-                    commands
-                        .entity(event.target)
-                        .remove::<(TradeInteraction, Buying)>();
-                }
-            } else {
-                let description = format!(
-                    "Trade with {} finished. Interaction ID: {}",
-                    trade.partner, interacting.id
-                );
-                add_log_writer.send(AddLogEntry::new(event.target, description.as_str()));
-                commands.entity(event.target).remove::<TradeInteraction>();
-            }
-        } else {
-            println!("No target agent found for event: {:?}", event);
+    for (seller, agent, seller_role, queue) in &seller_query {
+        let stock = agent.inventory.get_qty(seller_role.ware);
+        if stock == 0
+            || stock > trade_config.auction_below
+            || busy_query
+                .get(seller)
+                .is_ok_and(|(interacting, member)| interacting || member)
+        {
+            continue;
+        }
+
+        let bidders = free_bidders(queue, seller_role.ware, &buyer_query, &busy_query);
+        if bidders.len() < trade_config.min_bidders {
+            continue;
+        }
+
+        let auction = GroupInteraction::new(
+            GroupInteractionKind::Auction {
+                item: seller_role.ware,
+            },
+            seller,
+            seller_role.location,
+            interaction_config.max_group_participants,
+            interaction_config.group_gathering_secs,
+        )
+        .with_min_participants(trade_config.min_bidders + 1);
+
+        add_log_writer.send(AddLogEntry::new(
+            seller,
+            format!(
+                "Opening auction {} for the last {} {:?}",
+                auction.id, stock, seller_role.ware
+            )
+            .as_str(),
+        ));
+
+        let group = commands.spawn(auction).id();
+        for agent in std::iter::once(seller).chain(bidders) {
+            commands.trigger(JoinGroupInteraction { group, agent });
         }
     }
 }
 
-pub fn handle_interaction_timed_out(
-    trigger: Trigger<InteractionTimedOut>,
-    agent_query: Query<(Entity, &Interacting, &TradeNegotiation)>,
+// Buyers lining up while the auction gathers join it instead of asking for a
+// trade
+pub fn join_auctions_system(
+    group_query: Query<(Entity, &GroupInteraction)>,
+    queue_query: Query<&SellerQueue>,
+    buyer_query: Query<(&BuyTask, Has<Buying>, Has<WaitingInteraction>)>,
+    busy_query: Query<(Has<Interacting>, Has<GroupMember>)>,
+    mut commands: Commands,
+) {
+    for (group, auction) in &group_query {
+        let GroupInteractionKind::Auction { item } = auction.kind else {
+            continue;
+        };
+        if !auction.is_gathering() || auction.is_full() {
+            continue;
+        }
+        let Ok(queue) = queue_query.get(auction.host) else {
+            continue;
+        };
+
+        for agent in free_bidders(queue, item, &buyer_query, &busy_query) {
+            commands.trigger(JoinGroupInteraction { group, agent });
+        }
+    }
+}
+
+// Sealed bids: each bidder bids what it can afford, up to a cap. The best
+// bidders get one unit each and all pay the best losing bid, never less than
+// the usual price
+pub fn run_auction(
+    trigger: Trigger<GroupInteractionActive>,
+    mut group_query: Query<&mut GroupInteraction>,
+    mut agent_query: Query<&mut Agent>,
+    trade_config: Res<TradeConfig>,
+    government_config: Res<GovernmentConfig>,
     mut add_log_writer: EventWriter<AddLogEntry>,
-    mut trade_finalized_writer: EventWriter<TradeFinalized>,
+    mut commands: Commands,
 ) {
-    for (entity, interacting, _) in &agent_query {
-        if trigger.id == interacting.id {
-            add_log_writer.send(AddLogEntry::new(entity, "Trade -> Interaction timed out"));
+    let Ok(mut auction) = group_query.get_mut(trigger.group) else {
+        return;
+    };
+    let GroupInteractionKind::Auction { item } = auction.kind else {
+        return;
+    };
+    let seller = auction.host;
+
+    // nothing to bid for once the seller left
+    let Some(stock) = agent_query
+        .get(seller)
+        .ok()
+        .filter(|_| auction.contains(seller))
+        .map(|agent| agent.inventory.get_qty(item))
+    else {
+        commands.trigger(EndGroupInteraction {
+            group: trigger.group,
+            reason: InteractionEndReason::PartnerGone,
+        });
+        return;
+    };
 
-            trade_finalized_writer.send(TradeFinalized {
-                target: entity,
-                success: false,
+    // closed here, so the first ones leaving don't end it for the others
+    auction.set_finished();
+
+    let reserve = trade_config.unit_price(stock);
+    let max_bid = (reserve as f32 * trade_config.auction_max_markup).round() as usize;
+
+    let mut bids: Vec<(Entity, usize)> = auction
+        .participants()
+        .iter()
+        .filter(|bidder| **bidder != seller)
+        .filter_map(|bidder| {
+            let money = agent_query
+                .get(*bidder)
+                .ok()?
+                .inventory
+                .get_qty(ItemEnum::MONEY);
+            Some((*bidder, money.min(max_bid)))
+        })
+        .filter(|(_, bid)| *bid >= reserve)
+        .collect();
+    // ties go to the first one to join
+    bids.sort_by_key(|(_, bid)| Reverse(*bid));
+
+    let sold = stock.min(bids.len());
+    let price = bids.get(sold).map_or(reserve, |(_, bid)| *bid).max(reserve);
+    let winners: Vec<Entity> = bids[..sold].iter().map(|(bidder, _)| *bidder).collect();
+
+    for winner in &winners {
+        // bids are capped by what the bidders hold
+        if let Ok(mut agent) = agent_query.get_mut(*winner) {
+            if agent.inventory.remove(ItemEnum::MONEY, price).is_ok() {
+                agent.inventory.add(item, 1);
+            }
+        }
+    }
+
+    if sold > 0 {
+        let earned = price * sold;
+        let tax = government_config.tax_on(TaxKind::Sales, earned);
+        if let Ok(mut agent) = agent_query.get_mut(seller) {
+            agent.inventory.add(ItemEnum::MONEY, earned - tax);
+            if let Err(err) = agent.inventory.remove(item, sold) {
+                warn!("{} auctioned more than it had: {:?}", seller, err);
+            }
+        }
+        if tax > 0 {
+            commands.trigger(TaxCollected {
+                payer: seller,
+                kind: TaxKind::Sales,
+                amount: tax,
             });
         }
     }
+
+    add_log_writer.send(AddLogEntry::new(
+        seller,
+        format!(
+            "Auction {} -> sold {} {:?} at {} to {} of {} bidders",
+            auction.id,
+            sold,
+            item,
+            price,
+            winners.len(),
+            auction.participants().len() - 1
+        )
+        .as_str(),
+    ));
+
+    for participant in auction.participants() {
+        let reason = if winners.contains(participant) || (*participant == seller && sold > 0) {
+            InteractionEndReason::Completed
+        } else {
+            InteractionEndReason::Rejected(RejectionReason::Declined)
+        };
+
+        commands.trigger(LeaveGroupInteraction {
+            group: trigger.group,
+            agent: *participant,
+            reason,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::{components::AgentInteraction, trade::components::TradeInteraction};
+
+    #[derive(Resource, Default)]
+    struct EndedInteractions(Vec<InteractionEndReason>);
+
+    fn trade_app() -> App {
+        let mut app = App::new();
+        app.add_event::<AddLogEntry>()
+            .init_resource::<TradeConfig>()
+            .init_resource::<KnowledgeConfig>()
+            .init_resource::<SharedKnowledge>()
+            .init_resource::<GovernmentConfig>()
+            .init_resource::<EndedInteractions>()
+            .add_observer(seller_makes_offer)
+            .add_observer(buyer_evaluates_offer)
+            .add_observer(handle_offer_agreed)
+            .add_observer(handle_trade_finalized)
+            .add_observer(handle_interaction_ended)
+            .add_observer(
+                |trigger: Trigger<InteractionEnded>, mut ended: ResMut<EndedInteractions>| {
+                    ended.0.push(trigger.reason);
+                },
+            );
+        app
+    }
+
+    #[test]
+    fn ready_trade_swaps_inventories_and_completes() {
+        let mut app = trade_app();
+        let world = app.world_mut();
+
+        let seller = world
+            .spawn((Agent::new_seller_of(ItemEnum::WATER), Selling::new()))
+            .id();
+        let buyer = world
+            .spawn((Agent::new(), Buying::new(&ItemEnum::WATER, 1, seller)))
+            .id();
+
+        let mut interaction = AgentInteraction::new(1, buyer, seller);
+        interaction.set_ready();
+        let interaction = world.spawn(interaction).id();
+
+        let trade = TradeNegotiation {
+            partner: buyer,
+            role: TradeRole::Seller,
+            item: ItemEnum::WATER,
+            quantity: 1,
+            price: None,
+        };
+        world
+            .entity_mut(seller)
+            .insert(TradeInteraction::new(trade, interaction, 1));
+        world.entity_mut(buyer).insert(TradeInteraction::new(
+            trade.clone_for_source(seller),
+            interaction,
+            1,
+        ));
+
+        world.commands().trigger(InteractionReady {
+            id: 1,
+            source: buyer,
+            target: seller,
+        });
+        app.update();
+
+        let price = TradeConfig::default().unit_price(20);
+        let world = app.world();
+        let buyer_inventory = &world.get::<Agent>(buyer).unwrap().inventory;
+        let seller_inventory = &world.get::<Agent>(seller).unwrap().inventory;
+        assert_eq!(buyer_inventory.get_qty(ItemEnum::WATER), 1);
+        assert_eq!(buyer_inventory.get_qty(ItemEnum::MONEY), 20 - price);
+        assert_eq!(seller_inventory.get_qty(ItemEnum::WATER), 19);
+        assert!(seller_inventory.get_qty(ItemEnum::MONEY) > 20);
+
+        assert_eq!(
+            world.resource::<EndedInteractions>().0,
+            vec![InteractionEndReason::Completed]
+        );
+    }
 }
@@ -1,5 +1,5 @@
-pub mod plugin;
 pub mod components;
+mod events;
+pub mod plugin;
 mod resources;
 mod systems;
-mod events;
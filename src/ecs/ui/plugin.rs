@@ -3,7 +3,11 @@ use bevy_egui::EguiPlugin;
 
 use crate::ecs::ui::{
     resources::SelectedAgent,
-    systems::{agent_selection_system, agent_ui_panel_system, change_selected_entity},
+    systems::{
+        agent_selection_system, agent_ui_panel_system, change_selected_entity,
+        knowledge_diffusion_ui_system, money_supply_ui_system, resource_nodes_ui_system,
+        treasury_ui_system,
+    },
 };
 
 pub struct UiPlugin;
@@ -12,7 +16,17 @@ impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(EguiPlugin)
             .init_resource::<SelectedAgent>()
-            .add_systems(Update, (agent_selection_system, agent_ui_panel_system))
+            .add_systems(
+                Update,
+                (
+                    agent_selection_system,
+                    agent_ui_panel_system,
+                    knowledge_diffusion_ui_system,
+                    resource_nodes_ui_system,
+                    money_supply_ui_system,
+                    treasury_ui_system,
+                ),
+            )
             .add_observer(change_selected_entity);
     }
 }
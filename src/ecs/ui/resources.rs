@@ -1,4 +1,7 @@
-use bevy::{color::Color, ecs::{entity::Entity, system::Resource}};
+use bevy::{
+    color::Color,
+    ecs::{entity::Entity, system::Resource},
+};
 
 #[derive(Resource, Default)]
 pub struct SelectedAgent {
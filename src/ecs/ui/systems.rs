@@ -7,9 +7,10 @@ use bevy::{
         entity::Entity,
         observer::Trigger,
         query::With,
-        system::{Commands, Query, Res, ResMut},
+        system::{Commands, Query, Res, ResMut, SystemParam},
     },
     input::{mouse::MouseButton, ButtonInput},
+    log::debug,
     math::{primitives::InfinitePlane3d, Dir3, Vec3},
     render::camera::Camera,
     sprite::Sprite,
@@ -17,21 +18,37 @@ use bevy::{
     window::{PrimaryWindow, Window},
 };
 
+use crate::core::item::ItemEnum;
 use crate::ecs::{
+    action::components::Action,
+    building::components::{Building, Resident},
     buy::{actions::components::Buying, tasks::components::BuyTask},
-    components::{Interacting, WaitingInteraction},
+    components::{AgentInteraction, Interacting, WaitingInteraction},
     consume::{actions::components::Consuming, tasks::components::ConsumeTask},
+    credit::components::Debts,
+    economy::{actions::components::SellingBack, resources::MoneyStats},
+    employment::{
+        actions::components::{Applying, Working},
+        components::{Employee, Employer},
+    },
+    government::{components::Treasury, resources::TreasuryLedger},
+    harvest::{actions::components::Harvesting, components::ResourceNode},
+    interaction::group::components::GroupMember,
+    knowledge::{AgentKnowledge, SharedKnowledge},
+    movement::components::{MovementSpeed, TravelLog},
     sell::actions::components::Selling,
-    talk::{interaction::components::KnowledgeSharingInteraction, task::components::TalkTask},
+    spatial::SpatialGrid,
+    talk::{
+        gossip::resources::{KnowledgeDiffusion, DIFFUSION_MILESTONES},
+        interaction::components::KnowledgeSharingInteraction,
+        task::components::TalkTask,
+    },
     trade::components::TradeNegotiation,
     ui::{events::ChangeSelectedEntity, resources::SelectedAgent},
+    watchdog::components::Stuck,
 };
 use crate::{
-    ecs::{
-        agent::*,
-        components::{DurationAction, Idle},
-        logs::AgentLogs,
-    },
+    ecs::{agent::*, components::Idle, logs::AgentLogs},
     AgentInteractionQueue, Walking,
 };
 
@@ -63,6 +80,7 @@ pub fn agent_selection_system(
     cameras: Query<(&Camera, &GlobalTransform)>,
     mouse_buttons: Res<ButtonInput<MouseButton>>,
     mut agent_query: Query<(Entity, &Name, &Transform, &mut Sprite)>,
+    grid: Res<SpatialGrid>,
 ) {
     if contexts.ctx_mut().wants_pointer_input() {
         return;
@@ -102,24 +120,21 @@ pub fn agent_selection_system(
             }
         }
 
-        for (agent_entity, name, agent_transform, mut sprite) in &mut agent_query {
-            // The rest of the logic is the same, just using the new world_pos
-            let distance = world_pos
-                .truncate()
-                .distance(agent_transform.translation.truncate());
-            if distance < 32.0 {
-                println!("Selected agent {:?} - {:?}", agent_entity, name);
-                println!("Agent sprite color {:?}", sprite.color);
+        let picked = grid
+            .nearest(world_pos, 32.0, |entity| agent_query.contains(entity))
+            .map(|(entity, _)| entity);
 
-                let original_color = sprite.color;
-                sprite.color = Color::srgb(YELLOW.red, YELLOW.green, YELLOW.blue);
+        if let Some((agent_entity, name, _, mut sprite)) =
+            picked.and_then(|entity| agent_query.get_mut(entity).ok())
+        {
+            debug!("Selected agent {:?} - {:?}", agent_entity, name);
 
-                selected_agent.entity = Some((agent_entity, original_color));
+            let original_color = sprite.color;
+            sprite.color = Color::srgb(YELLOW.red, YELLOW.green, YELLOW.blue);
 
-                clicked_on_agent = true;
+            selected_agent.entity = Some((agent_entity, original_color));
 
-                break;
-            }
+            clicked_on_agent = true;
         }
 
         if !clicked_on_agent {
@@ -128,24 +143,81 @@ pub fn agent_selection_system(
     }
 }
 
+// Markers of what the selected agent is doing
+type AgentMarkers = (
+    Option<&'static Idle>,
+    Option<&'static Consuming>,
+    Option<&'static Selling>,
+    Option<&'static Buying>,
+    Option<&'static Walking>,
+    Option<&'static Harvesting>,
+    Option<&'static Applying>,
+    Option<&'static Working>,
+    Option<&'static SellingBack>,
+    Option<&'static Stuck>,
+);
+
+// What the inspector shows about the selected agent's current markers,
+// task and interaction
+#[derive(SystemParam)]
+pub struct AgentActivityQueries<'w, 's> {
+    action_query: Query<'w, 's, AgentMarkers>,
+    task_query: Query<
+        'w,
+        's,
+        (
+            Option<&'static BuyTask>,
+            Option<&'static ConsumeTask>,
+            Option<&'static TalkTask>,
+        ),
+    >,
+    interaction_query: Query<
+        'w,
+        's,
+        (
+            Option<&'static Interacting>,
+            Option<&'static WaitingInteraction>,
+            Option<&'static GroupMember>,
+        ),
+    >,
+    interaction_entity_query: Query<'w, 's, &'static AgentInteraction>,
+    interaction_data_query: Query<
+        'w,
+        's,
+        (
+            Option<&'static TradeNegotiation>,
+            Option<&'static KnowledgeSharingInteraction>,
+        ),
+    >,
+}
+
+// What the inspector shows about the selected agent's life: movement, home,
+// job, debts and knowledge
+#[derive(SystemParam)]
+pub struct AgentDetailsQueries<'w, 's> {
+    knowledge_query: Query<'w, 's, &'static AgentKnowledge>,
+    movement_query: Query<'w, 's, (&'static MovementSpeed, &'static TravelLog)>,
+    resident_query: Query<'w, 's, &'static Resident>,
+    building_query: Query<'w, 's, &'static Building>,
+    employment_query: Query<
+        'w,
+        's,
+        (
+            Option<&'static Employee>,
+            Option<&'static Employer>,
+            Option<&'static Debts>,
+        ),
+    >,
+    shared_knowledge: Res<'w, SharedKnowledge>,
+}
+
 pub fn agent_ui_panel_system(
     mut contexts: EguiContexts,
     mut commands: Commands,
     selected_agent: Res<SelectedAgent>,
     agent_query: Query<(&Agent, &Name, &AgentInteractionQueue, &AgentLogs)>,
-    action_query: Query<(
-        Option<&Idle>,
-        Option<&Consuming>,
-        Option<&Selling>,
-        Option<&Buying>,
-        Option<&Walking>,
-    )>,
-    task_query: Query<(Option<&BuyTask>, Option<&ConsumeTask>, Option<&TalkTask>)>,
-    interaction_query: Query<(Option<&Interacting>, Option<&WaitingInteraction>)>,
-    interaction_data_query: Query<(
-        Option<&TradeNegotiation>,
-        Option<&KnowledgeSharingInteraction>,
-    )>,
+    activity: AgentActivityQueries,
+    details: AgentDetailsQueries,
     frame_count: Res<FrameCount>,
 ) {
     // Check if an agent is selected. If not, we don't draw anything.
@@ -168,8 +240,18 @@ pub fn agent_ui_panel_system(
             ui.label(format!("Frame: {}", frame_count.0));
 
             ui.label("CURRENT MARKERS:");
-            if let Ok((idle, consuming, selling, buying, walking)) =
-                action_query.get(selected_entity)
+            if let Ok((
+                idle,
+                consuming,
+                selling,
+                buying,
+                walking,
+                harvesting,
+                applying,
+                working,
+                selling_back,
+                stuck,
+            )) = activity.action_query.get(selected_entity)
             {
                 if let Some(_) = idle {
                     ui.label("State: Idle 😴".to_string());
@@ -178,14 +260,14 @@ pub fn agent_ui_panel_system(
                 if let Some(v) = consuming {
                     ui.label(format!(
                         "State: Consuming 🍔 - {:.1}",
-                        v.get_resting_duration()
+                        v.lifecycle().get_resting_duration().unwrap_or_default()
                     ));
                 }
 
                 if let Some(v) = selling {
                     ui.label(format!(
                         "State: Selling 💰 - {:.1}",
-                        v.get_resting_duration()
+                        v.lifecycle().get_resting_duration().unwrap_or_default()
                     ));
                 }
 
@@ -199,11 +281,39 @@ pub fn agent_ui_panel_system(
                         w.destination.x, w.destination.y
                     ));
                 }
+
+                if let Some(v) = harvesting {
+                    ui.label(format!(
+                        "State: Harvesting {} ⛏ - {:.1}",
+                        v.amount,
+                        v.lifecycle().get_resting_duration().unwrap_or_default()
+                    ));
+                }
+
+                if let Some(v) = applying {
+                    ui.label(format!("State: Applying for a job at {} 📝", v.employer));
+                }
+
+                if let Some(v) = selling_back {
+                    ui.label(format!("State: Selling {} {:?} back 💱", v.qty, v.item));
+                }
+
+                if let Some(v) = working {
+                    ui.label(format!(
+                        "State: Working for {} 🔨 - {:.1}",
+                        v.employer,
+                        v.lifecycle().get_resting_duration().unwrap_or_default()
+                    ));
+                }
+
+                if let Some(v) = stuck {
+                    ui.label(format!("STUCK in {:?} for {:.1}s ⚠", v.stuck_in, v.seconds));
+                }
             }
             ui.separator();
 
             ui.label("CURRENT TASK:");
-            if let Ok((buy, consume, knowledge)) = task_query.get(selected_entity) {
+            if let Ok((buy, consume, knowledge)) = activity.task_query.get(selected_entity) {
                 if let Some(_) = buy {
                     ui.label("Buy Task");
                 }
@@ -223,27 +333,31 @@ pub fn agent_ui_panel_system(
             ui.separator();
 
             ui.label("CURRENT Interaction:");
-            if let Ok((interacting, waiting_interaction)) = interaction_query.get(selected_entity) {
-                if let Some(v) = interacting {
+            if let Ok((interacting, waiting_interaction, group_member)) =
+                activity.interaction_query.get(selected_entity)
+            {
+                if let Some(v) = interacting
+                    .and_then(|v| activity.interaction_entity_query.get(v.interaction).ok())
+                {
                     ui.label(format!(
-                        "Interacting {} {:.1}",
+                        "Interacting {} {:?} {:.1}",
                         v.id,
-                        v.get_resting_duration()
+                        v.phase(),
+                        v.lifecycle().get_resting_duration().unwrap_or_default()
                     ));
                     if ui.button("Select partner").clicked() {
-                        if v.target == selected_entity {
-                            commands.trigger(ChangeSelectedEntity { target: v.source });
-                        } else {
-                            commands.trigger(ChangeSelectedEntity { target: v.target });
-                        }
+                        commands.trigger(ChangeSelectedEntity {
+                            target: v.partner_of(selected_entity),
+                        });
                     };
                 }
 
                 if let Some(w) = waiting_interaction {
                     ui.label(format!(
-                        "Waiting Interaction {} {:.1}",
+                        "Waiting Interaction {} {:?} {:.1}",
                         w.id,
-                        w.get_resting_duration()
+                        w.phase(),
+                        w.lifecycle().get_resting_duration().unwrap_or_default()
                     ));
                     if ui.button("Select partner").clicked() {
                         if w.target == selected_entity {
@@ -253,11 +367,16 @@ pub fn agent_ui_panel_system(
                         }
                     };
                 }
+
+                if let Some(g) = group_member {
+                    ui.label(format!("Group Interaction {}", g.id));
+                }
             }
             ui.separator();
 
             ui.label("CURRENT Interaction Data:");
-            if let Ok((trade, knowledge_interaction)) = interaction_data_query.get(selected_entity)
+            if let Ok((trade, knowledge_interaction)) =
+                activity.interaction_data_query.get(selected_entity)
             {
                 if let Some(v) = trade {
                     ui.label("TradeNegotiation");
@@ -286,8 +405,98 @@ pub fn agent_ui_panel_system(
             ui.label("DETAILS:");
             ui.label(format!("Hunger: {:.1}/1000", agent.needs.hunger));
             ui.label(format!("Thirst: {:.1}/1000", agent.needs.thirst));
+            if let Ok((speed, travel_log)) = details.movement_query.get(selected_entity) {
+                ui.label(format!(
+                    "Speed: {:.0} (base {:.0})",
+                    speed.current, speed.base
+                ));
+                ui.label(format!(
+                    "Travelled: {:.0} in {:.0}s",
+                    travel_log.distance, travel_log.walking_secs
+                ));
+            }
+            if let Some(home) = details
+                .resident_query
+                .get(selected_entity)
+                .ok()
+                .and_then(|resident| details.building_query.get(resident.home).ok())
+            {
+                ui.label(format!(
+                    "Home: {:?} {}x{} at {:.0} ({}/{} residents)",
+                    home.kind,
+                    home.footprint.x,
+                    home.footprint.y,
+                    home.entrance,
+                    home.occupants().len(),
+                    home.capacity
+                ));
+                if home.owner == Some(selected_entity) {
+                    ui.label("Owns the place");
+                }
+            }
+            if let Ok((employee, employer, debts)) = details.employment_query.get(selected_entity) {
+                if let Some(v) = employee {
+                    ui.label(format!(
+                        "Employed by {} for {} per shift ({} shifts)",
+                        v.employer, v.wage, v.shifts
+                    ));
+                }
+                if let Some(v) = employer {
+                    ui.label(format!(
+                        "Employs {}/{} workers at {} per shift",
+                        v.workers().len(),
+                        v.max_workers,
+                        v.wage
+                    ));
+                }
+                for iou in debts.into_iter().flat_map(Debts::ious) {
+                    ui.label(format!(
+                        "Owes {} to {} by {:.0}s (borrowed {})",
+                        iou.owed, iou.creditor, iou.due_at, iou.principal
+                    ));
+                }
+            }
             ui.separator();
 
+            // --- Display Agent's Knowledge ---
+            if let Ok(knowledge) = details.knowledge_query.get(selected_entity) {
+                ui.label("KNOWLEDGE:");
+                for item in ItemEnum::ALL {
+                    for (seller, id) in knowledge.get_sellers_of(&item, &details.shared_knowledge) {
+                        let price = knowledge
+                            .get_price_at(seller, &item, &details.shared_knowledge)
+                            .map(|v| format!("{:.1}", v))
+                            .unwrap_or("?".to_string());
+                        ui.label(format!(
+                            "- {:?} seller {} at {} ({:.0}%)",
+                            item,
+                            seller,
+                            price,
+                            knowledge.confidence_of(&id) * 100.
+                        ));
+                    }
+                    for (location, _) in
+                        knowledge.get_resource_sites_of(&item, &details.shared_knowledge)
+                    {
+                        ui.label(format!("- {:?} site at {:.0}", item, location));
+                    }
+                }
+                for (location, _) in knowledge.get_markets(&details.shared_knowledge) {
+                    ui.label(format!("- Market at {:.0}", location));
+                }
+                if let Some(location) =
+                    knowledge.get_home_of(selected_entity, &details.shared_knowledge)
+                {
+                    ui.label(format!("- Home at {:.0}", location));
+                }
+                for (creditor, amount) in
+                    knowledge.get_debts_of(selected_entity, &details.shared_knowledge)
+                {
+                    ui.label(format!("- Owes {} to {}", amount, creditor));
+                }
+                ui.separator();
+            }
+
             // --- Display Inventory ---
             ui.label("INVENTORY:");
             let items_list = &agent.inventory.list();
@@ -318,3 +527,111 @@ pub fn agent_ui_panel_system(
                 });
         });
 }
+
+pub fn knowledge_diffusion_ui_system(
+    mut contexts: EguiContexts,
+    diffusion: Res<KnowledgeDiffusion>,
+) {
+    egui::Window::new("Knowledge diffusion")
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(format!("Population: {}", diffusion.population));
+            ui.separator();
+
+            let mut ids: Vec<_> = diffusion.facts.keys().collect();
+            ids.sort();
+
+            for id in ids {
+                let fact = &diffusion.facts[id];
+                let milestones: Vec<String> = DIFFUSION_MILESTONES
+                    .iter()
+                    .zip(fact.milestones.iter())
+                    .map(|(milestone, reached)| match reached {
+                        Some(secs) => format!("{:.0}%: {:.1}s", milestone * 100., secs),
+                        None => format!("{:.0}%: -", milestone * 100.),
+                    })
+                    .collect();
+
+                ui.label(format!(
+                    "Fact {}{}: {:.1}% ({}) - {}",
+                    id,
+                    if fact.false_fact { " (false)" } else { "" },
+                    diffusion.coverage(id) * 100.,
+                    fact.known_by,
+                    milestones.join(", ")
+                ));
+            }
+        });
+}
+
+pub fn resource_nodes_ui_system(mut contexts: EguiContexts, node_query: Query<&ResourceNode>) {
+    egui::Window::new("Resources")
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            for node in &node_query {
+                ui.label(format!(
+                    "{:?} at {:.0}: {}/{:.0} {:?}{}",
+                    node.kind,
+                    node.location,
+                    node.stock(),
+                    node.max_stock,
+                    node.kind.item(),
+                    if node.is_depleted() {
+                        " (depleted)"
+                    } else {
+                        ""
+                    }
+                ));
+            }
+        });
+}
+
+pub fn money_supply_ui_system(mut contexts: EguiContexts, stats: Res<MoneyStats>) {
+    egui::Window::new("Money supply")
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(format!("Minted by stipends: {}", stats.minted));
+            if let Some(latest) = stats.latest() {
+                ui.label(format!(
+                    "Total: {} - held by sellers: {} - treasury: {} - broke agents: {}",
+                    latest.total, latest.held_by_sellers, latest.treasury, latest.broke
+                ));
+            }
+            ui.separator();
+
+            for sample in stats.samples().rev() {
+                ui.label(format!(
+                    "{:.0}s: {} ({} sellers, {} broke)",
+                    sample.secs, sample.total, sample.held_by_sellers, sample.broke
+                ));
+            }
+        });
+}
+
+pub fn treasury_ui_system(
+    mut contexts: EguiContexts,
+    treasury_query: Query<&Treasury>,
+    ledger: Res<TreasuryLedger>,
+) {
+    egui::Window::new("Treasury")
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            if let Ok(treasury) = treasury_query.get_single() {
+                ui.label(format!("Funds: {}", treasury.funds()));
+            }
+            ui.label(format!("Public wells built: {}", ledger.wells_built));
+            ui.separator();
+
+            let today = &ledger.today;
+            for totals in std::iter::once(today).chain(ledger.days().rev()) {
+                ui.label(format!(
+                    "Day {}: +{} sales tax, +{} income tax, -{} welfare, -{} public works",
+                    totals.day,
+                    totals.sales_tax,
+                    totals.income_tax,
+                    totals.welfare,
+                    totals.public_works
+                ));
+            }
+        });
+}
@@ -0,0 +1,120 @@
+use bevy::{ecs::world::Command, prelude::*};
+
+use crate::ecs::{
+    action::resources::ActionRegistry,
+    components::{AgentInteraction, Idle, Interacting, WaitingInteraction},
+    interaction::{
+        common::{
+            components::{AgentInteractionQueue, InteractionEndReason},
+            events::InteractionEnded,
+        },
+        group::{components::GroupMember, events::LeaveGroupInteraction},
+    },
+    logs::AddLogEntry,
+    talk::interaction::components::KnowledgeSharingInteraction,
+    task::{components::TaskFailure, events::TaskFailed, resources::TaskRegistry},
+    trade::components::TradeNegotiation,
+    watchdog::components::Stuck,
+};
+
+/// Puts a stuck agent back to Idle: fails its task, cancels its actions and
+/// leaves every interaction it is part of, notifying the other side.
+pub struct ResetAgent {
+    pub target: Entity,
+}
+
+impl Command for ResetAgent {
+    fn apply(self, world: &mut World) {
+        let task_registry = world.resource::<TaskRegistry>().clone();
+        let action_registry = world.resource::<ActionRegistry>().clone();
+
+        let Ok(entity) = world.get_entity(self.target) else {
+            return;
+        };
+
+        let tasks = task_registry.find_on(&entity);
+        let interacting = entity
+            .get::<Interacting>()
+            .and_then(|v| world.get::<AgentInteraction>(v.interaction))
+            .map(|v| (v.id, v.source, v.target));
+        let waiting = entity.get::<WaitingInteraction>().map(|v| (v.id, v.target));
+        let group = entity.get::<GroupMember>().map(|v| v.group);
+
+        for kind in tasks {
+            world.trigger(TaskFailed {
+                target: self.target,
+                kind,
+                reason: TaskFailure::Stuck,
+            });
+        }
+
+        let Ok(mut entity) = world.get_entity_mut(self.target) else {
+            return;
+        };
+
+        action_registry.cancel_all_on(&mut entity);
+        for id in task_registry.ids() {
+            entity.remove_by_id(id);
+        }
+
+        // sources waiting for this agent must not wait until their timeout
+        let queued = match entity.get_mut::<AgentInteractionQueue>() {
+            Some(mut queue) => {
+                queue.clean_ready_interaction();
+                queue.drain()
+            }
+            None => vec![],
+        };
+
+        entity
+            .remove::<(
+                Interacting,
+                WaitingInteraction,
+                TradeNegotiation,
+                KnowledgeSharingInteraction,
+                Stuck,
+            )>()
+            .insert(Idle);
+
+        world.send_event(AddLogEntry::new(
+            self.target,
+            "Watchdog -> agent reset to Idle",
+        ));
+
+        for item in queued {
+            world.trigger(InteractionEnded {
+                id: item.id,
+                source: item.source(),
+                target: self.target,
+                reason: InteractionEndReason::PartnerGone,
+            });
+        }
+
+        // the target may have started it already
+        if let Some((id, target)) = waiting {
+            world.trigger(InteractionEnded {
+                id,
+                source: self.target,
+                target,
+                reason: InteractionEndReason::PartnerGone,
+            });
+        }
+
+        if let Some(group) = group {
+            world.trigger(LeaveGroupInteraction {
+                group,
+                agent: self.target,
+                reason: InteractionEndReason::PartnerGone,
+            });
+        }
+
+        if let Some((id, source, target)) = interacting {
+            world.trigger(InteractionEnded {
+                id,
+                source,
+                target,
+                reason: InteractionEndReason::PartnerGone,
+            });
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StuckIn {
+    Task,
+    Action,
+    WaitingInteraction,
+    Interaction,
+}
+
+// Set on agents the watchdog flagged, removed as soon as their state changes
+#[derive(Component, Debug)]
+pub struct Stuck {
+    pub stuck_in: StuckIn,
+    pub seconds: f32,
+}
@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+use crate::ecs::{
+    game_state::GameState,
+    watchdog::{
+        resources::{ActionProgress, WatchdogConfig},
+        systems::*,
+    },
+};
+
+pub struct WatchdogPlugin;
+
+impl Plugin for WatchdogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WatchdogConfig>()
+            .init_resource::<ActionProgress>()
+            .add_systems(
+                Last,
+                (record_action_progress_system, stuck_agents_watchdog_system)
+                    .chain()
+                    .run_if(in_state(GameState::Running)),
+            );
+    }
+}
@@ -0,0 +1,47 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::ecs::action::components::ActionKind;
+
+/// Limits (in seconds) an agent can stay in the very same state before
+/// being flagged as stuck
+#[derive(Resource, Debug, Clone)]
+pub struct WatchdogConfig {
+    pub max_task_secs: f32,
+    pub max_action_secs: f32,
+    pub max_waiting_interaction_secs: f32,
+    pub max_interaction_secs: f32,
+    // reset stuck agents back to Idle, cleaning their interactions
+    pub reset_stuck_agents: bool,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            max_task_secs: 120.,
+            max_action_secs: 120.,
+            max_waiting_interaction_secs: 20.,
+            max_interaction_secs: 30.,
+            reset_stuck_agents: true,
+        }
+    }
+}
+
+// Time spent in each agent's actions, as last reported by ActionProgressed
+#[derive(Resource, Debug, Default)]
+pub struct ActionProgress {
+    elapsed: HashMap<(Entity, ActionKind), f32>,
+}
+
+impl ActionProgress {
+    pub fn record(&mut self, agent: Entity, kind: ActionKind, elapsed: f32) {
+        self.elapsed.insert((agent, kind), elapsed);
+    }
+
+    pub fn elapsed(&self, agent: Entity, kind: ActionKind) -> Option<f32> {
+        self.elapsed.get(&(agent, kind)).copied()
+    }
+
+    pub fn retain_agents(&mut self, keep: impl Fn(Entity) -> bool) {
+        self.elapsed.retain(|(agent, _), _| keep(*agent));
+    }
+}
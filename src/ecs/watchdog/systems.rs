@@ -0,0 +1,191 @@
+use bevy::{
+    ecs::{component::Components, system::SystemParam},
+    prelude::*,
+    utils::HashMap,
+};
+
+use crate::ecs::{
+    action::{components::ActionKind, events::ActionProgressed, resources::ActionRegistry},
+    agent::Agent,
+    components::{Interacting, InteractionId, WaitingInteraction},
+    interaction::common::components::AgentInteractionQueue,
+    logs::AddLogEntry,
+    task::{components::TaskKind, resources::TaskRegistry},
+    watchdog::{
+        commands::ResetAgent,
+        components::{Stuck, StuckIn},
+        resources::{ActionProgress, WatchdogConfig},
+    },
+};
+
+#[derive(Debug, PartialEq)]
+pub struct StateSignature {
+    tasks: Vec<TaskKind>,
+    actions: Vec<ActionKind>,
+    waiting_interaction: Option<InteractionId>,
+    interaction: Option<InteractionId>,
+}
+
+impl StateSignature {
+    fn stuck_in(&self) -> Option<StuckIn> {
+        if self.interaction.is_some() {
+            Some(StuckIn::Interaction)
+        } else if self.waiting_interaction.is_some() {
+            Some(StuckIn::WaitingInteraction)
+        } else if !self.actions.is_empty() {
+            Some(StuckIn::Action)
+        } else if !self.tasks.is_empty() {
+            Some(StuckIn::Task)
+        } else {
+            // Idle
+            None
+        }
+    }
+}
+
+// Everything the watchdog reads to describe the state an agent is in
+#[derive(SystemParam)]
+pub struct AgentStateReader<'w> {
+    task_registry: Res<'w, TaskRegistry>,
+    action_registry: Res<'w, ActionRegistry>,
+    progress: Res<'w, ActionProgress>,
+    components: &'w Components,
+}
+
+impl AgentStateReader<'_> {
+    fn signature(&self, agent: &EntityRef) -> StateSignature {
+        StateSignature {
+            tasks: self.task_registry.find_on(agent),
+            actions: self.action_registry.find_on(agent),
+            waiting_interaction: agent.get::<WaitingInteraction>().map(|v| v.id),
+            interaction: agent.get::<Interacting>().map(|v| v.id),
+        }
+    }
+
+    fn diagnostic_snapshot(&self, agent: &EntityRef, signature: &StateSignature) -> String {
+        let mut names: Vec<String> = agent
+            .archetype()
+            .components()
+            .filter_map(|id| self.components.get_name(id))
+            .map(|name| name.rsplit("::").next().unwrap_or(name).to_string())
+            .collect();
+        names.sort();
+
+        let mut snapshot = format!("Components: [{}]", names.join(", "));
+
+        for kind in &signature.actions {
+            if let Some(elapsed) = self.progress.elapsed(agent.id(), *kind) {
+                snapshot.push_str(&format!(" {:?} for {:.1}s", kind, elapsed));
+            }
+        }
+
+        if let Some(interacting) = agent.get::<Interacting>() {
+            snapshot.push_str(&format!(" {:?}", interacting));
+        }
+
+        if let Some(waiting) = agent.get::<WaitingInteraction>() {
+            snapshot.push_str(&format!(" {:?}", waiting));
+        }
+
+        if let Some(queue) = agent.get::<AgentInteractionQueue>() {
+            let ids: Vec<InteractionId> = queue.list().map(|item| item.id).collect();
+            snapshot.push_str(&format!(" Queue: {:?}", ids));
+        }
+
+        snapshot
+    }
+}
+
+pub struct AgentWatch {
+    signature: StateSignature,
+    seconds: f32,
+}
+
+pub fn record_action_progress_system(
+    mut progressed_reader: EventReader<ActionProgressed>,
+    agent_query: Query<(), With<Agent>>,
+    mut progress: ResMut<ActionProgress>,
+) {
+    progress.retain_agents(|agent| agent_query.contains(agent));
+
+    for progressed in progressed_reader.read() {
+        progress.record(progressed.target, progressed.kind, progressed.elapsed);
+    }
+}
+
+pub fn stuck_agents_watchdog_system(
+    query: Query<EntityRef, With<Agent>>,
+    state_reader: AgentStateReader,
+    config: Res<WatchdogConfig>,
+    time: Res<Time>,
+    mut watches: Local<HashMap<Entity, AgentWatch>>,
+    mut add_log_writer: EventWriter<AddLogEntry>,
+    mut commands: Commands,
+) {
+    watches.retain(|entity, _| query.contains(*entity));
+
+    for agent in &query {
+        let entity = agent.id();
+        let signature = state_reader.signature(&agent);
+
+        let watch = watches.entry(entity).or_insert(AgentWatch {
+            signature: StateSignature {
+                tasks: vec![],
+                actions: vec![],
+                waiting_interaction: None,
+                interaction: None,
+            },
+            seconds: 0.,
+        });
+
+        if watch.signature != signature {
+            watch.signature = signature;
+            watch.seconds = 0.;
+
+            if agent.contains::<Stuck>() {
+                commands.entity(entity).remove::<Stuck>();
+            }
+            continue;
+        }
+
+        watch.seconds += time.delta_secs();
+
+        let Some(stuck_in) = watch.signature.stuck_in() else {
+            continue;
+        };
+
+        let limit = match stuck_in {
+            StuckIn::Task => config.max_task_secs,
+            StuckIn::Action => config.max_action_secs,
+            StuckIn::WaitingInteraction => config.max_waiting_interaction_secs,
+            StuckIn::Interaction => config.max_interaction_secs,
+        };
+
+        if watch.seconds <= limit || agent.contains::<Stuck>() {
+            continue;
+        }
+
+        let snapshot = state_reader.diagnostic_snapshot(&agent, &watch.signature);
+        warn!(
+            "Watchdog -> agent {} stuck in {:?} for {:.1}s: {:?}. {}",
+            entity, stuck_in, watch.seconds, watch.signature, snapshot
+        );
+        add_log_writer.send(AddLogEntry::new(
+            entity,
+            format!(
+                "Watchdog -> stuck in {:?} for {:.1}s. {}",
+                stuck_in, watch.seconds, snapshot
+            )
+            .as_str(),
+        ));
+
+        if config.reset_stuck_agents {
+            commands.queue(ResetAgent { target: entity });
+        } else {
+            commands.entity(entity).insert(Stuck {
+                stuck_in,
+                seconds: watch.seconds,
+            });
+        }
+    }
+}
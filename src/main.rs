@@ -6,28 +6,50 @@ use bevy::log::*;
 use bevy::prelude::*;
 
 use crate::core::item::ItemEnum;
+use crate::ecs::action::components::{Action, ActionFailure};
+use crate::ecs::action::plugin::{ActionPlugin, RegisterAction};
 use crate::ecs::agent::*;
+use crate::ecs::building::components::{BuildingKind, Resident};
+use crate::ecs::building::plugin::BuildingPlugin;
+use crate::ecs::building::utils::spawn_building;
 use crate::ecs::buy::plugin::BuyPlugin;
 use crate::ecs::buy::tasks::components::BuyTask;
 use crate::ecs::components::*;
 use crate::ecs::consume::plugin::ConsumePlugin;
 use crate::ecs::consume::tasks::components::ConsumeTask;
+use crate::ecs::credit::plugin::CreditPlugin;
+use crate::ecs::crowd::components::SellerQueue;
+use crate::ecs::crowd::plugin::CrowdPlugin;
+use crate::ecs::economy::plugin::EconomyPlugin;
+use crate::ecs::employment::plugin::EmploymentPlugin;
 use crate::ecs::game_state::*;
+use crate::ecs::government::plugin::GovernmentPlugin;
+use crate::ecs::harvest::plugin::HarvestPlugin;
 use crate::ecs::interaction::{
     common::{components::*, events::*},
     plugin::*,
 };
+use crate::ecs::knowledge::Honesty;
 use crate::ecs::knowledge::KnowledgePlugin;
 use crate::ecs::knowledge::SharedKnowledge;
 use crate::ecs::logs::*;
+use crate::ecs::map::components::Path;
+use crate::ecs::map::plugin::MapPlugin;
+use crate::ecs::map::resources::WorldMap;
+use crate::ecs::movement::components::MovementSpeed;
+use crate::ecs::movement::plugin::MovementPlugin;
+use crate::ecs::movement::resources::MovementConfig;
 use crate::ecs::roles::none::NoneRole;
 use crate::ecs::roles::plugin::RolesPlugin;
 use crate::ecs::roles::seller::SellerRole;
 use crate::ecs::sell::plugin::SellPlugin;
+use crate::ecs::spatial::SpatialPlugin;
 use crate::ecs::talk::plugin::TalkPlugin;
+use crate::ecs::task::commands::TaskCommandsExt;
+use crate::ecs::task::plugin::TaskPlugin;
 use crate::ecs::trade::plugin::TradePlugin;
 use crate::ecs::ui::plugin::UiPlugin;
-use crate::ecs::utils::get_random_vec3;
+use crate::ecs::watchdog::plugin::WatchdogPlugin;
 
 fn toggle_pause(
     keys: Res<ButtonInput<KeyCode>>,
@@ -51,15 +73,29 @@ fn main() {
         }))
         .init_state::<GameState>()
         .add_event::<AddLogEntry>()
+        .add_plugins(MapPlugin)
+        .add_plugins(BuildingPlugin)
+        .add_plugins(HarvestPlugin)
+        .add_plugins(ActionPlugin)
+        .add_plugins(TaskPlugin)
         .add_plugins(TradePlugin)
         .add_plugins(BaseInteractionPlugin)
         .add_plugins(TalkPlugin)
         .add_plugins(ConsumePlugin)
         .add_plugins(KnowledgePlugin)
+        .add_plugins(SpatialPlugin)
+        .add_plugins(CrowdPlugin)
+        .add_plugins(MovementPlugin)
         .add_plugins(RolesPlugin)
+        .add_plugins(EmploymentPlugin)
+        .add_plugins(EconomyPlugin)
+        .add_plugins(GovernmentPlugin)
+        .add_plugins(CreditPlugin)
         .add_plugins(SellPlugin)
         .add_plugins(BuyPlugin)
         .add_plugins(UiPlugin)
+        .add_plugins(WatchdogPlugin)
+        .register_action::<Walking>()
         .add_systems(Startup, setup)
         .add_systems(
             First,
@@ -129,6 +165,7 @@ fn setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut shared_knowledge: ResMut<SharedKnowledge>,
+    mut map: ResMut<WorldMap>,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
 ) {
     commands.spawn(Camera2d);
@@ -143,7 +180,12 @@ fn setup(
     for i in 0..5 {
         let entity_id = commands.spawn_empty().id();
 
-        let v = get_random_vec3();
+        let Some((shop, v)) =
+            spawn_building(&mut commands, &mut map, BuildingKind::Shop, &[entity_id])
+        else {
+            commands.entity(entity_id).despawn();
+            continue;
+        };
 
         commands.entity(entity_id).insert((
             Sprite {
@@ -158,15 +200,22 @@ fn setup(
             Transform::from_scale(scale).with_translation(v),
             AnimationConfig::new(),
             AgentInteractionQueue::new(),
+            Hostility::default(),
             Name::new(format!("the happier meat seller {}", i)),
             AgentLogs::new(),
-            SellerRole { location: v },
+            SellerRole {
+                shop,
+                location: v,
+                ware: ItemEnum::MEAT,
+            },
+            SellerQueue::default(),
+            Honesty::new(0.5),
             Idle,
         ));
 
         shared_knowledge.add_fact(ecs::knowledge::KnowledgeFact::SellerInfo {
             entity: entity_id,
-            location: v,
+            shop,
             wares: vec![ItemEnum::MEAT],
         });
     }
@@ -174,7 +223,15 @@ fn setup(
     for i in 0..5 {
         let entity_id = commands.spawn_empty().id();
 
-        let v = get_random_vec3();
+        let Some((stall, v)) = spawn_building(
+            &mut commands,
+            &mut map,
+            BuildingKind::MarketStall,
+            &[entity_id],
+        ) else {
+            commands.entity(entity_id).despawn();
+            continue;
+        };
 
         commands.entity(entity_id).insert((
             Sprite {
@@ -189,19 +246,38 @@ fn setup(
             Transform::from_scale(scale).with_translation(v),
             AnimationConfig::new(),
             AgentInteractionQueue::new(),
+            Hostility::default(),
             Name::new(format!("the happier water seller {}", i)),
             AgentLogs::new(),
-            SellerRole { location: v },
+            SellerRole {
+                shop: stall,
+                location: v,
+                ware: ItemEnum::WATER,
+            },
+            SellerQueue::default(),
+            Honesty::new(0.5),
             Idle,
         ));
 
         shared_knowledge.add_fact(ecs::knowledge::KnowledgeFact::SellerInfo {
             entity: entity_id,
-            location: v,
+            shop: stall,
             wares: vec![ItemEnum::WATER],
         });
+        shared_knowledge.add_fact(ecs::knowledge::KnowledgeFact::Market { building: stall });
     }
 
+    for kind in [
+        BuildingKind::Well,
+        BuildingKind::Well,
+        BuildingKind::Workshop,
+        BuildingKind::Workshop,
+    ] {
+        spawn_building(&mut commands, &mut map, kind, &[]);
+    }
+
+    let mut agents = vec![];
+
     for i in 0..500 {
         let entity_id = commands.spawn_empty().id();
 
@@ -218,47 +294,53 @@ fn setup(
             Transform::from_scale(scale).with_translation(Vec3::new(100., 100., 0.)),
             AnimationConfig::new(),
             AgentInteractionQueue::new(),
+            Hostility::default(),
             AgentLogs::new(),
             Name::new(format!("agent_{}", i)),
             NoneRole,
             Idle,
         ));
+
+        agents.push(entity_id);
+    }
+
+    // The first ones live together in houses, the others have no home yet
+    for residents in agents.chunks(BuildingKind::House.capacity()).take(20) {
+        let Some((home, _)) =
+            spawn_building(&mut commands, &mut map, BuildingKind::House, residents)
+        else {
+            break;
+        };
+
+        for resident in residents {
+            commands.entity(*resident).insert(Resident { home });
+        }
     }
 }
 
 fn check_idle_agents_needs(
     query: Query<(Entity, &Agent, &Idle)>,
-    mut add_log_writer: EventWriter<AddLogEntry>,
+    map: Res<WorldMap>,
     mut commands: Commands,
 ) {
     for (entity, agent, _) in &query {
         if agent.is_hungry() {
             if agent.have_food() {
-                add_log_writer.send(AddLogEntry::new(entity, "Start ConsumeTask (eat)"));
-                commands
-                    .entity(entity)
-                    .insert(ConsumeTask::new(core::item::ItemEnum::MEAT, 1))
-                    .remove::<Idle>();
+                commands.start_task(
+                    entity,
+                    ConsumeTask::new(core::item::ItemEnum::MEAT, 1, map.random_passable_point()),
+                );
             } else {
-                add_log_writer.send(AddLogEntry::new(entity, "Start BuyTask"));
-                commands
-                    .entity(entity)
-                    .insert(BuyTask::new(core::item::ItemEnum::MEAT, 1))
-                    .remove::<Idle>();
+                commands.start_task(entity, BuyTask::new(core::item::ItemEnum::MEAT, 1));
             }
         } else if agent.is_thirsty() {
             if agent.have_drink() {
-                add_log_writer.send(AddLogEntry::new(entity, "Start ConsumeTask (drink)"));
-                commands
-                    .entity(entity)
-                    .insert(ConsumeTask::new(core::item::ItemEnum::WATER, 1))
-                    .remove::<Idle>();
+                commands.start_task(
+                    entity,
+                    ConsumeTask::new(core::item::ItemEnum::WATER, 1, map.random_passable_point()),
+                );
             } else {
-                add_log_writer.send(AddLogEntry::new(entity, "Start BuyTask (water)"));
-                commands
-                    .entity(entity)
-                    .insert(BuyTask::new(core::item::ItemEnum::WATER, 1))
-                    .remove::<Idle>();
+                commands.start_task(entity, BuyTask::new(core::item::ItemEnum::WATER, 1));
             }
         }
     }
@@ -270,23 +352,58 @@ fn handle_walking_action(
         &mut Transform,
         &AnimationConfig,
         &mut Sprite,
-        &Walking,
+        &mut Walking,
     )>,
+    mut path_query: Query<&mut Path>,
+    speed_query: Query<&MovementSpeed>,
+    map: Res<WorldMap>,
+    movement_config: Res<MovementConfig>,
     time: Res<Time>,
     mut commands: Commands,
-    mut add_log_writer: EventWriter<AddLogEntry>,
 ) {
-    for (entity, mut transform, config, mut sprite, walking) in &mut query {
-        if walking.destination.distance(transform.translation) > 50. {
-            let mut direction = (walking.destination - transform.translation).normalize();
-            movement(&mut direction, &mut transform, &config, &mut sprite, &time);
-        } else {
-            add_log_writer.send(AddLogEntry::new(entity, "Walking finished with success"));
-            if walking.should_set_idle_at_completion() {
-                commands.entity(entity).insert(Idle).remove::<Walking>();
-            } else {
-                commands.entity(entity).remove::<Walking>();
+    for (entity, mut transform, config, mut sprite, mut walking) in &mut query {
+        if !walking.lifecycle().is_in_progress() {
+            continue;
+        }
+
+        let arrival_radius = walking
+            .arrival_radius
+            .unwrap_or(movement_config.arrival_radius);
+        if walking.destination.distance(transform.translation) > arrival_radius {
+            // Waits for plan_paths_system
+            let (Ok(mut path), Ok(speed)) = (path_query.get_mut(entity), speed_query.get(entity))
+            else {
+                continue;
+            };
+
+            while path.next().is_some_and(|waypoint| {
+                waypoint.distance(transform.translation) < map.tile_size / 2.
+            }) {
+                path.advance();
+                walking.blocked_replans = 0;
             }
+
+            let target = path.next().unwrap_or(walking.destination);
+            let mut direction = (target - transform.translation).normalize();
+            if !movement(
+                &mut direction,
+                &mut transform,
+                config,
+                &mut sprite,
+                speed.current,
+                &map,
+                &time,
+            ) {
+                walking.blocked_replans += 1;
+                if walking.blocked_replans > movement_config.max_blocked_replans {
+                    walking.lifecycle_mut().fail(ActionFailure::Blocked);
+                } else {
+                    // Re-planned from here on the next frame
+                    commands.entity(entity).remove::<Path>();
+                }
+            }
+        } else {
+            walking.lifecycle_mut().complete();
         }
     }
 }
@@ -296,10 +413,10 @@ fn movement(
     transform: &mut Transform,
     config: &AnimationConfig,
     sprite: &mut Sprite,
+    speed: f32,
+    map: &WorldMap,
     time: &Res<Time>,
-) {
-    let speed = 125.0;
-
+) -> bool {
     if direction.length_squared() > 0.0 {
         if let Some(atlas) = &mut sprite.texture_atlas {
             if direction.y > 0.0 {
@@ -338,6 +455,12 @@ fn movement(
             }
         };
 
-        transform.translation += direction.normalize() * speed * time.delta_secs();
+        let step = direction.normalize() * speed * time.delta_secs();
+        match map.step(transform.translation, step) {
+            Some(to) => transform.translation = to,
+            None => return false,
+        }
     }
+
+    true
 }